@@ -0,0 +1,97 @@
+use crate::{
+    contracts::hooks::types::Types,
+    format_error, panic_error,
+    types::{metadata::StandardHookMetadata, HyperlaneMessage},
+};
+use scrypto::prelude::*;
+
+#[blueprint]
+mod aggregation_hook {
+
+    struct AggregationHook {
+        hooks: Vec<ComponentAddress>,
+    }
+
+    impl AggregationHook {
+        /// Instantiates an aggregation hook that calls all given hooks in order.
+        /// The child hooks see this component as their caller. Hooks that restrict
+        /// `post_dispatch` to their parent (like the `RateLimitedHook`) have to be instantiated
+        /// with this component as parent. The `MerkleTreeHook` also reads the local domain from
+        /// its parent, so it can't be aggregated. Configure it as the required hook instead.
+        pub fn instantiate(hooks: Vec<ComponentAddress>) -> Global<AggregationHook> {
+            if hooks.is_empty() {
+                panic_error!("at least one hook is required");
+            }
+
+            Self { hooks }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize()
+        }
+
+        pub fn hook_type(&self) -> Types {
+            Types::AGGREGATION
+        }
+
+        pub fn hooks(&self) -> Vec<ComponentAddress> {
+            self.hooks.clone()
+        }
+
+        /// Post-dispatch passes the payment through every child hook in order. Each hook
+        /// consumes what it needs and the leftover buckets are handed to the next one.
+        /// We return the leftover buckets that have not been consumed by any hook.
+        pub fn post_dispatch(
+            &mut self,
+            metadata: Option<StandardHookMetadata>,
+            message: HyperlaneMessage,
+            payment: Vec<FungibleBucket>,
+        ) -> Vec<FungibleBucket> {
+            let mut payment = payment;
+
+            for hook in self.hooks.iter() {
+                let result = ScryptoVmV1Api::object_call(
+                    hook.as_node_id(),
+                    "post_dispatch",
+                    scrypto_args!(metadata.clone(), message.clone(), payment),
+                );
+                payment = scrypto_decode(&result)
+                    .expect(&format_error!("failed to decode post_dispatch result"));
+            }
+
+            payment
+        }
+
+        /// Quote dispatch returns the sum of all child hook quotes, merged per resource
+        pub fn quote_dispatch(
+            &self,
+            metadata: Option<StandardHookMetadata>,
+            message: HyperlaneMessage,
+        ) -> IndexMap<ResourceAddress, Decimal> {
+            let mut quote: IndexMap<ResourceAddress, Decimal> = IndexMap::new();
+
+            for hook in self.hooks.iter() {
+                let result = ScryptoVmV1Api::object_call(
+                    hook.as_node_id(),
+                    "quote_dispatch",
+                    scrypto_args!(metadata.clone(), message.clone()),
+                );
+
+                let hook_quote: IndexMap<ResourceAddress, Decimal> = scrypto_decode(&result)
+                    .expect(&format_error!("failed to decode quote_dispatch result"));
+
+                for (key, value) in hook_quote.iter() {
+                    quote
+                        .entry(*key)
+                        .and_modify(|existing: &mut Decimal| {
+                            *existing = existing
+                                .checked_add(*value)
+                                .expect(&format_error!("encountered overflow on quote_dispatch"))
+                        })
+                        .or_insert(*value);
+                }
+            }
+
+            quote
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    contracts::hooks::types::Types,
+    format_error,
+    types::{metadata::StandardHookMetadata, warp_payload::WarpPayload, HyperlaneMessage},
+};
+use scrypto::prelude::*;
+
+#[blueprint]
+mod amount_routing_hook {
+
+    struct AmountRoutingHook {
+        // hook for transfers below the threshold
+        lower: ComponentAddress,
+        // hook for transfers at or above the threshold
+        upper: ComponentAddress,
+        threshold: Decimal,
+        // divisibility of the token amounts in the warp payload
+        divisibility: u32,
+    }
+
+    impl AmountRoutingHook {
+        /// The divisibility has to match the divisibility of the token that is sent.
+        /// Child hooks see this component as their caller, see "AggregationHook::instantiate()".
+        pub fn instantiate(
+            lower: ComponentAddress,
+            upper: ComponentAddress,
+            threshold: Decimal,
+            divisibility: u32,
+        ) -> Global<AmountRoutingHook> {
+            Self {
+                lower,
+                upper,
+                threshold,
+                divisibility,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        pub fn hook_type(&self) -> Types {
+            Types::AMOUNTROUTING
+        }
+
+        pub fn lower(&self) -> ComponentAddress {
+            self.lower
+        }
+
+        pub fn upper(&self) -> ComponentAddress {
+            self.upper
+        }
+
+        pub fn threshold(&self) -> Decimal {
+            self.threshold
+        }
+
+        /// Post-dispatch forwards the message and payment to the hook selected by the
+        /// transfer amount and returns the leftover buckets of that hook.
+        pub fn post_dispatch(
+            &mut self,
+            metadata: Option<StandardHookMetadata>,
+            message: HyperlaneMessage,
+            payment: Vec<FungibleBucket>,
+        ) -> Vec<FungibleBucket> {
+            let hook = self.route(&message);
+
+            let result = ScryptoVmV1Api::object_call(
+                hook.as_node_id(),
+                "post_dispatch",
+                scrypto_args!(metadata, message, payment),
+            );
+
+            scrypto_decode(&result).expect(&format_error!("failed to decode post_dispatch result"))
+        }
+
+        /// Quote dispatch returns the quote of the hook selected by the transfer amount
+        pub fn quote_dispatch(
+            &self,
+            metadata: Option<StandardHookMetadata>,
+            message: HyperlaneMessage,
+        ) -> IndexMap<ResourceAddress, Decimal> {
+            let hook = self.route(&message);
+
+            let result = ScryptoVmV1Api::object_call(
+                hook.as_node_id(),
+                "quote_dispatch",
+                scrypto_args!(metadata, message),
+            );
+
+            scrypto_decode(&result).expect(&format_error!("failed to decode quote_dispatch result"))
+        }
+
+        fn route(&self, message: &HyperlaneMessage) -> ComponentAddress {
+            let warp_payload = WarpPayload::try_from(message.body.clone())
+                .expect(&format_error!("failed to decode warp payload"));
+
+            if warp_payload.get_amount(self.divisibility) >= self.threshold {
+                self.upper
+            } else {
+                self.lower
+            }
+        }
+    }
+}
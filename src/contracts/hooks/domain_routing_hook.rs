@@ -0,0 +1,122 @@
+use crate::{
+    contracts::hooks::types::Types,
+    format_error,
+    types::{metadata::StandardHookMetadata, HyperlaneMessage},
+};
+use scrypto::prelude::*;
+
+#[blueprint]
+mod domain_routing_hook {
+
+    enable_method_auth! {
+        methods {
+            // Public
+            hook_type => PUBLIC;
+            hook => PUBLIC;
+            post_dispatch => PUBLIC;
+            quote_dispatch => PUBLIC;
+
+            // Owner only
+            set_hook => restrict_to: [OWNER];
+            remove_hook => restrict_to: [OWNER];
+        }
+    }
+
+    struct DomainRoutingHook {
+        // map from destination domain -> hook
+        hooks: KeyValueStore<u32, ComponentAddress>,
+    }
+
+    impl DomainRoutingHook {
+        /// Child hooks see this component as their caller, see "AggregationHook::instantiate()".
+        pub fn instantiate(
+            initial_hooks: Vec<(u32, ComponentAddress)>,
+        ) -> (Global<DomainRoutingHook>, FungibleBucket) {
+            // reserve an address for the component
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(DomainRoutingHook::blueprint_id());
+
+            // create new owner badge
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .metadata(metadata!(init {
+                    "name" => "Domain Routing Hook Owner Badge", locked;
+                    "component" => component_address, locked;
+                }))
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            let hooks: KeyValueStore<u32, ComponentAddress> = KeyValueStore::new();
+            for (domain, hook) in initial_hooks {
+                hooks.insert(domain, hook);
+            }
+
+            let component = Self { hooks }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
+                    owner_badge.resource_address()
+                ))))
+                .with_address(address_reservation)
+                .globalize();
+
+            (component, owner_badge)
+        }
+
+        pub fn hook_type(&self) -> Types {
+            Types::ROUTING
+        }
+
+        /// Returns the hook configured for the given destination domain
+        pub fn hook(&self, destination: u32) -> Option<ComponentAddress> {
+            self.hooks.get(&destination).map(|hook| *hook)
+        }
+
+        pub fn set_hook(&mut self, destination: u32, hook: ComponentAddress) {
+            self.hooks.insert(destination, hook);
+        }
+
+        pub fn remove_hook(&mut self, destination: u32) {
+            self.hooks.remove(&destination);
+        }
+
+        /// Post-dispatch forwards the message and payment to the hook configured for the
+        /// message destination and returns the leftover buckets of that hook.
+        pub fn post_dispatch(
+            &mut self,
+            metadata: Option<StandardHookMetadata>,
+            message: HyperlaneMessage,
+            payment: Vec<FungibleBucket>,
+        ) -> Vec<FungibleBucket> {
+            let hook = self.route(message.destination);
+
+            let result = ScryptoVmV1Api::object_call(
+                hook.as_node_id(),
+                "post_dispatch",
+                scrypto_args!(metadata, message, payment),
+            );
+
+            scrypto_decode(&result).expect(&format_error!("failed to decode post_dispatch result"))
+        }
+
+        /// Quote dispatch returns the quote of the hook configured for the message destination
+        pub fn quote_dispatch(
+            &self,
+            metadata: Option<StandardHookMetadata>,
+            message: HyperlaneMessage,
+        ) -> IndexMap<ResourceAddress, Decimal> {
+            let hook = self.route(message.destination);
+
+            let result = ScryptoVmV1Api::object_call(
+                hook.as_node_id(),
+                "quote_dispatch",
+                scrypto_args!(metadata, message),
+            );
+
+            scrypto_decode(&result).expect(&format_error!("failed to decode quote_dispatch result"))
+        }
+
+        fn route(&self, destination: u32) -> ComponentAddress {
+            self.hook(destination)
+                .expect(&format_error!("no hook for destination {}", destination))
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    contracts::{hooks::types::Types, mailbox::DEFAULT_HOOK_METADATA},
+    format_error, panic_error,
+    types::{metadata::StandardHookMetadata, HyperlaneMessage},
+};
+use scrypto::prelude::*;
+
+#[blueprint]
+mod fallback_routing_hook {
+
+    enable_method_auth! {
+        methods {
+            // Public
+            hook_type => PUBLIC;
+            hook => PUBLIC;
+            mailbox => PUBLIC;
+            fallback_hook => PUBLIC;
+            post_dispatch => PUBLIC;
+            quote_dispatch => PUBLIC;
+            // Owner only
+            set_hook => restrict_to: [OWNER];
+            remove_hook => restrict_to: [OWNER];
+        }
+    }
+
+    struct FallbackRoutingHook {
+        // map from destination domain -> hook
+        hooks: KeyValueStore<u32, ComponentAddress>,
+        parent: ComponentAddress,
+    }
+
+    impl FallbackRoutingHook {
+        /// Child hooks see this component as their caller, see "AggregationHook::instantiate()".
+        pub fn instantiate(
+            parent: ComponentAddress,
+            initial_hooks: Vec<(u32, ComponentAddress)>,
+        ) -> (Global<FallbackRoutingHook>, FungibleBucket) {
+            // reserve an address for the component
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(FallbackRoutingHook::blueprint_id());
+
+            // create new owner badge
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .metadata(metadata!(init {
+                    "name" => "Fallback Routing Hook Owner Badge", locked;
+                    "component" => component_address, locked;
+                }))
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            let hooks: KeyValueStore<u32, ComponentAddress> = KeyValueStore::new();
+            for (domain, hook) in initial_hooks {
+                hooks.insert(domain, hook);
+            }
+
+            let component = Self { hooks, parent }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
+                    owner_badge.resource_address()
+                ))))
+                .with_address(address_reservation)
+                .globalize();
+
+            (component, owner_badge)
+        }
+
+        pub fn hook_type(&self) -> Types {
+            Types::FALLBACKROUTING
+        }
+
+        pub fn mailbox(&self) -> ComponentAddress {
+            self.parent
+        }
+
+        /// Returns the hook configured for the given destination domain
+        pub fn hook(&self, destination: u32) -> Option<ComponentAddress> {
+            self.hooks.get(&destination).map(|hook| *hook)
+        }
+
+        /// Returns the current default hook of the mailbox
+        pub fn fallback_hook(&self) -> Option<ComponentAddress> {
+            Self::mailbox_default_hook(self.parent)
+        }
+
+        pub fn set_hook(&mut self, destination: u32, hook: ComponentAddress) {
+            self.hooks.insert(destination, hook);
+        }
+
+        pub fn remove_hook(&mut self, destination: u32) {
+            self.hooks.remove(&destination);
+        }
+
+        /// Post-dispatch forwards the message and payment to the hook configured for the
+        /// message destination, or to the mailbox default hook if there is none.
+        /// We return the leftover buckets of that hook.
+        pub fn post_dispatch(
+            &mut self,
+            metadata: Option<StandardHookMetadata>,
+            message: HyperlaneMessage,
+            payment: Vec<FungibleBucket>,
+        ) -> Vec<FungibleBucket> {
+            let hook = self.route(message.destination);
+
+            let result = ScryptoVmV1Api::object_call(
+                hook.as_node_id(),
+                "post_dispatch",
+                scrypto_args!(metadata, message, payment),
+            );
+
+            scrypto_decode(&result).expect(&format_error!("failed to decode post_dispatch result"))
+        }
+
+        /// Quote dispatch returns the quote of the hook the message would be routed to
+        pub fn quote_dispatch(
+            &self,
+            metadata: Option<StandardHookMetadata>,
+            message: HyperlaneMessage,
+        ) -> IndexMap<ResourceAddress, Decimal> {
+            let hook = self.route(message.destination);
+
+            let result = ScryptoVmV1Api::object_call(
+                hook.as_node_id(),
+                "quote_dispatch",
+                scrypto_args!(metadata, message),
+            );
+
+            scrypto_decode(&result).expect(&format_error!("failed to decode quote_dispatch result"))
+        }
+
+        fn route(&self, destination: u32) -> ComponentAddress {
+            if let Some(hook) = self.hook(destination) {
+                return hook;
+            }
+
+            let fallback_hook = self
+                .fallback_hook()
+                .expect(&format_error!("no hook for destination {}", destination));
+            if fallback_hook == Runtime::global_address() {
+                panic_error!("fallback hook can not be the routing hook itself");
+            }
+
+            fallback_hook
+        }
+
+        /// The mailbox state is locked while it dispatches a message, so the default hook is
+        /// read from the metadata of the mailbox instead of calling "Mailbox::default_hook()".
+        fn mailbox_default_hook(mailbox: ComponentAddress) -> Option<ComponentAddress> {
+            let mailbox: Global<AnyComponent> = mailbox.into();
+            let default_hook: Option<GlobalAddress> = mailbox
+                .get_metadata(DEFAULT_HOOK_METADATA)
+                .expect(&format_error!("failed to decode default_hook metadata"));
+
+            default_hook.map(|address| {
+                ComponentAddress::try_from(address)
+                    .expect(&format_error!("default hook is not a component"))
+            })
+        }
+    }
+}
@@ -1,3 +1,10 @@
+pub mod aggregation_hook;
+pub mod amount_routing_hook;
+pub mod domain_routing_hook;
+pub mod fallback_routing_hook;
 pub mod interchain_gas_paymaster;
 pub mod merkle_tree_hook;
+pub mod pausable_hook;
+pub mod protocol_fee_hook;
+pub mod rate_limited_hook;
 pub mod types;
@@ -0,0 +1,123 @@
+use scrypto::prelude::*;
+
+use crate::{
+    contracts::hooks::types::Types,
+    panic_error,
+    types::{metadata::StandardHookMetadata, HyperlaneMessage},
+};
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct PausedEvent {}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct UnpausedEvent {}
+
+#[blueprint]
+#[events(PausedEvent, UnpausedEvent)]
+mod pausable_hook {
+
+    enable_method_auth! {
+        roles {
+            pauser => updatable_by: [OWNER];
+        },
+        methods {
+            // Public
+            hook_type => PUBLIC;
+            paused => PUBLIC;
+            post_dispatch => PUBLIC;
+            quote_dispatch => PUBLIC;
+
+            // Pauser or owner
+            pause => restrict_to: [pauser, OWNER];
+
+            // Owner only
+            unpause => restrict_to: [OWNER];
+        }
+    }
+
+    struct PausableHook {
+        paused: bool,
+    }
+
+    impl PausableHook {
+        /// The pauser badge can only pause the hook, unpausing is reserved for the owner.
+        pub fn instantiate(
+            pauser_badge: ResourceAddress,
+        ) -> (Global<PausableHook>, FungibleBucket) {
+            // reserve an address for the component
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(PausableHook::blueprint_id());
+
+            // create new owner badge
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .metadata(metadata!(init {
+                    "name" => "Pausable Hook Owner Badge", locked;
+                    "component" => component_address, locked;
+                }))
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            let component = Self { paused: false }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
+                    owner_badge.resource_address()
+                ))))
+                .roles(roles! {
+                    pauser => rule!(require(pauser_badge));
+                })
+                .with_address(address_reservation)
+                .globalize();
+
+            (component, owner_badge)
+        }
+
+        pub fn hook_type(&self) -> Types {
+            Types::PAUSABLE
+        }
+
+        pub fn paused(&self) -> bool {
+            self.paused
+        }
+
+        pub fn pause(&mut self) {
+            if self.paused {
+                panic_error!("already paused");
+            }
+            self.paused = true;
+
+            Runtime::emit_event(PausedEvent {});
+        }
+
+        pub fn unpause(&mut self) {
+            if !self.paused {
+                panic_error!("not paused");
+            }
+            self.paused = false;
+
+            Runtime::emit_event(UnpausedEvent {});
+        }
+
+        /// Post dispatch rejects every message while the hook is paused.
+        /// The payment is not touched and returned as is.
+        pub fn post_dispatch(
+            &mut self,
+            _metadata: Option<StandardHookMetadata>,
+            _message: HyperlaneMessage,
+            payment: Vec<FungibleBucket>,
+        ) -> Vec<FungibleBucket> {
+            if self.paused {
+                panic_error!("paused");
+            }
+
+            payment
+        }
+
+        pub fn quote_dispatch(
+            &self,
+            _metadata: Option<StandardHookMetadata>,
+            _message: HyperlaneMessage,
+        ) -> IndexMap<ResourceAddress, Decimal> {
+            IndexMap::new()
+        }
+    }
+}
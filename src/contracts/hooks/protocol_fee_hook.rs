@@ -0,0 +1,194 @@
+use scrypto::prelude::*;
+
+use crate::{
+    contracts::hooks::types::Types,
+    format_error, panic_error,
+    types::{metadata::StandardHookMetadata, HyperlaneMessage},
+};
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct ProtocolFeeSetEvent {
+    pub protocol_fee: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct BeneficiarySetEvent {
+    pub beneficiary: ComponentAddress,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct FeesCollectedEvent {
+    pub beneficiary: ComponentAddress,
+    pub amount: Decimal,
+}
+
+#[blueprint]
+#[events(ProtocolFeeSetEvent, BeneficiarySetEvent, FeesCollectedEvent)]
+mod protocol_fee_hook {
+
+    enable_method_auth! {
+        methods {
+            // Public
+            hook_type => PUBLIC;
+            protocol_fee => PUBLIC;
+            max_protocol_fee => PUBLIC;
+            beneficiary => PUBLIC;
+            post_dispatch => PUBLIC;
+            quote_dispatch => PUBLIC;
+
+            // Owner only
+            set_protocol_fee => restrict_to: [OWNER];
+            set_beneficiary => restrict_to: [OWNER];
+            collect_fees => restrict_to: [OWNER];
+        }
+    }
+
+    struct ProtocolFeeHook {
+        // resource address the protocol fee is paid in
+        resource_address: ResourceAddress,
+
+        // the vault holds the fees until they are collected
+        vault: FungibleVault,
+
+        // fixed fee charged for every dispatched message
+        protocol_fee: Decimal,
+
+        // upper bound for the protocol fee, can't be changed after instantiation
+        max_protocol_fee: Decimal,
+
+        // account that receives the collected fees
+        beneficiary: ComponentAddress,
+    }
+
+    impl ProtocolFeeHook {
+        pub fn instantiate(
+            resource: ResourceAddress,
+            max_protocol_fee: Decimal,
+            protocol_fee: Decimal,
+            beneficiary: ComponentAddress,
+        ) -> (Global<ProtocolFeeHook>, FungibleBucket) {
+            Self::assert_valid_fee(protocol_fee, max_protocol_fee);
+
+            // reserve an address for the component
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(ProtocolFeeHook::blueprint_id());
+
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .metadata(metadata!(init {
+                    "name" => "ProtocolFeeHook Owner Badge", locked;
+                    "component" => component_address, locked;
+                }))
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            let component = Self {
+                resource_address: resource,
+                vault: FungibleVault::new(resource),
+                protocol_fee,
+                max_protocol_fee,
+                beneficiary,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Fixed(rule!(require(
+                owner_badge.resource_address()
+            ))))
+            .with_address(address_reservation)
+            .globalize();
+
+            (component, owner_badge)
+        }
+
+        pub fn hook_type(&self) -> Types {
+            Types::PROTOCOLFEE
+        }
+
+        pub fn protocol_fee(&self) -> Decimal {
+            self.protocol_fee
+        }
+
+        pub fn max_protocol_fee(&self) -> Decimal {
+            self.max_protocol_fee
+        }
+
+        pub fn beneficiary(&self) -> ComponentAddress {
+            self.beneficiary
+        }
+
+        pub fn set_protocol_fee(&mut self, protocol_fee: Decimal) {
+            Self::assert_valid_fee(protocol_fee, self.max_protocol_fee);
+            self.protocol_fee = protocol_fee;
+
+            Runtime::emit_event(ProtocolFeeSetEvent { protocol_fee });
+        }
+
+        pub fn set_beneficiary(&mut self, beneficiary: ComponentAddress) {
+            self.beneficiary = beneficiary;
+
+            Runtime::emit_event(BeneficiarySetEvent { beneficiary });
+        }
+
+        /// Sends all collected fees to the beneficiary account
+        pub fn collect_fees(&mut self) {
+            let fees = self.vault.take_all();
+            let amount = fees.amount();
+
+            let mut account: Global<Account> = self.beneficiary.into();
+            account.try_deposit_or_abort(fees.into(), None);
+
+            Runtime::emit_event(FeesCollectedEvent {
+                beneficiary: self.beneficiary,
+                amount,
+            });
+        }
+
+        /// Post dispatch takes the protocol fee from the bucket of the fee resource.
+        /// We return the left over Buckets that have not been consumed
+        pub fn post_dispatch(
+            &mut self,
+            _metadata: Option<StandardHookMetadata>,
+            _message: HyperlaneMessage,
+            payment: Vec<FungibleBucket>,
+        ) -> Vec<FungibleBucket> {
+            if self.protocol_fee.is_zero() {
+                return payment;
+            }
+
+            let mut payment = payment;
+            let resource_payment = payment
+                .iter_mut()
+                .find(|x| x.resource_address() == self.resource_address)
+                .expect(&format_error!("no payment found for resource address"));
+
+            if resource_payment.amount() < self.protocol_fee {
+                panic_error!(
+                    "insufficient payment for protocol fee. fee: {}",
+                    self.protocol_fee
+                )
+            }
+            self.vault.put(resource_payment.take(self.protocol_fee));
+
+            payment
+        }
+
+        pub fn quote_dispatch(
+            &self,
+            _metadata: Option<StandardHookMetadata>,
+            _message: HyperlaneMessage,
+        ) -> IndexMap<ResourceAddress, Decimal> {
+            IndexMap::from_iter([(self.resource_address, self.protocol_fee)])
+        }
+
+        fn assert_valid_fee(protocol_fee: Decimal, max_protocol_fee: Decimal) {
+            if protocol_fee.is_negative() {
+                panic_error!("protocol fee can not be negative")
+            }
+            if protocol_fee > max_protocol_fee {
+                panic_error!(
+                    "protocol fee {} exceeds max protocol fee {}",
+                    protocol_fee,
+                    max_protocol_fee
+                )
+            }
+        }
+    }
+}
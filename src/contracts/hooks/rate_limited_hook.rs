@@ -0,0 +1,248 @@
+use scrypto::prelude::*;
+
+use crate::{
+    contracts::hooks::types::Types,
+    format_error, panic_error,
+    types::{metadata::StandardHookMetadata, warp_payload::WarpPayload, Bytes32, HyperlaneMessage},
+};
+
+/// Time in which an empty rate limit refills to the max amount
+pub const RATE_LIMIT_DURATION: i64 = 86_400; // 24h
+
+#[derive(ScryptoSbor, Clone)]
+pub struct RateLimit {
+    // amount that can still be sent at "last_updated"
+    pub filled_level: Decimal,
+    // unix timestamp in seconds
+    pub last_updated: i64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct RateLimitConsumedEvent {
+    pub sender: Bytes32,
+    pub amount: Decimal,
+    pub filled_level: Decimal,
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub struct SenderLimit {
+    // max amount the sender can dispatch within 24h
+    pub max_amount: Decimal,
+    // divisibility of the token amounts in the warp payload of the sender
+    pub divisibility: u32,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SenderLimitSetEvent {
+    pub sender: Bytes32,
+    pub max_amount: Decimal,
+    pub divisibility: u32,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SenderLimitRemovedEvent {
+    pub sender: Bytes32,
+}
+
+#[blueprint]
+#[events(RateLimitConsumedEvent, SenderLimitSetEvent, SenderLimitRemovedEvent)]
+mod rate_limited_hook {
+
+    enable_method_auth! {
+        roles {
+            parent_component => updatable_by: [];
+        },
+        methods {
+            // Public
+            hook_type => PUBLIC;
+            mailbox => PUBLIC;
+            sender_limit => PUBLIC;
+            current_level => PUBLIC;
+            quote_dispatch => PUBLIC;
+            // Mailbox Only
+            post_dispatch => restrict_to: [parent_component];
+            // Owner only
+            set_sender_limit => restrict_to: [OWNER];
+            remove_sender_limit => restrict_to: [OWNER];
+        }
+    }
+
+    struct RateLimitedHook {
+        parent: ComponentAddress,
+        // map from message sender -> configured limit
+        sender_limits: KeyValueStore<Bytes32, SenderLimit>,
+        // map from message sender -> rate limit
+        limits: KeyValueStore<Bytes32, RateLimit>,
+    }
+
+    impl RateLimitedHook {
+        /// The rate limit is tracked per message sender, which is usually a "HypToken" component.
+        /// Only senders with a limit, see "set_sender_limit()", are rate limited. Messages of
+        /// all other senders pass through, so the hook can be the required hook of a mailbox.
+        pub fn instantiate(parent: ComponentAddress) -> (Global<RateLimitedHook>, FungibleBucket) {
+            // reserve an address for the component
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(RateLimitedHook::blueprint_id());
+
+            // create new owner badge
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .metadata(metadata!(init {
+                    "name" => "Rate Limited Hook Owner Badge", locked;
+                    "component" => component_address, locked;
+                }))
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            // Create mailbox component rule to ensure that the "post_dispatch()" function can only
+            // be called by the mailbox itself.
+            let mailbox_component_rule =
+                rule!(require(NonFungibleGlobalId::global_caller_badge(parent)));
+
+            let component = Self {
+                parent,
+                sender_limits: KeyValueStore::new(),
+                limits: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
+                owner_badge.resource_address()
+            ))))
+            .roles(roles! {
+                parent_component => mailbox_component_rule;
+            })
+            .with_address(address_reservation)
+            .globalize();
+
+            (component, owner_badge)
+        }
+
+        pub fn hook_type(&self) -> Types {
+            Types::RATELIMITED
+        }
+
+        pub fn mailbox(&self) -> ComponentAddress {
+            self.parent
+        }
+
+        pub fn sender_limit(&self, sender: Bytes32) -> Option<SenderLimit> {
+            self.sender_limits.get(&sender).map(|x| x.clone())
+        }
+
+        /// Rate limits the warp transfers of the given sender. The divisibility has to match
+        /// the divisibility of the token the sender transfers. Changing the max amount does not
+        /// touch the current level of the sender, it is capped to the new max amount on the
+        /// next refill.
+        pub fn set_sender_limit(
+            &mut self,
+            sender: Bytes32,
+            max_amount: Decimal,
+            divisibility: u32,
+        ) {
+            if !max_amount.is_positive() {
+                panic_error!("max amount must be positive");
+            }
+            self.sender_limits.insert(
+                sender,
+                SenderLimit {
+                    max_amount,
+                    divisibility,
+                },
+            );
+
+            Runtime::emit_event(SenderLimitSetEvent {
+                sender,
+                max_amount,
+                divisibility,
+            });
+        }
+
+        /// Removes the limit of the given sender, its messages pass through afterwards
+        pub fn remove_sender_limit(&mut self, sender: Bytes32) {
+            if self.sender_limits.remove(&sender).is_none() {
+                return;
+            }
+            self.limits.remove(&sender);
+
+            Runtime::emit_event(SenderLimitRemovedEvent { sender });
+        }
+
+        /// Returns the amount the given sender can currently dispatch,
+        /// or None if the sender is not rate limited
+        pub fn current_level(&self, sender: Bytes32) -> Option<Decimal> {
+            let sender_limit = self.sender_limit(sender)?;
+            let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+            Some(self.refilled_level(sender, &sender_limit, now))
+        }
+
+        pub fn post_dispatch(
+            &mut self,
+            _metadata: Option<StandardHookMetadata>,
+            message: HyperlaneMessage,
+            payment: Vec<FungibleBucket>,
+        ) -> Vec<FungibleBucket> {
+            let Some(sender_limit) = self.sender_limit(message.sender) else {
+                return payment;
+            };
+
+            let warp_payload = WarpPayload::try_from(message.body)
+                .expect(&format_error!("failed to decode warp payload"));
+            let amount = warp_payload.get_amount(sender_limit.divisibility);
+
+            let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+            let level = self.refilled_level(message.sender, &sender_limit, now);
+            if amount > level {
+                panic_error!(
+                    "rate limit exceeded. amount: {}, available: {}",
+                    amount,
+                    level
+                );
+            }
+
+            let filled_level = level - amount;
+            self.limits.insert(
+                message.sender,
+                RateLimit {
+                    filled_level,
+                    last_updated: now,
+                },
+            );
+
+            Runtime::emit_event(RateLimitConsumedEvent {
+                sender: message.sender,
+                amount,
+                filled_level,
+            });
+
+            payment
+        }
+
+        pub fn quote_dispatch(
+            &self,
+            _metadata: Option<StandardHookMetadata>,
+            _message: HyperlaneMessage,
+        ) -> IndexMap<ResourceAddress, Decimal> {
+            IndexMap::new()
+        }
+
+        /// Senders without a rate limit entry start with a full bucket.
+        /// The bucket refills linearly and is full again after "RATE_LIMIT_DURATION".
+        fn refilled_level(&self, sender: Bytes32, sender_limit: &SenderLimit, now: i64) -> Decimal {
+            let max_amount = sender_limit.max_amount;
+            let Some(limit) = self.limits.get(&sender).map(|x| x.clone()) else {
+                return max_amount;
+            };
+
+            let elapsed = (now - limit.last_updated).clamp(0, RATE_LIMIT_DURATION);
+            let refill = max_amount
+                .checked_mul(elapsed)
+                .and_then(|x| x.checked_div(RATE_LIMIT_DURATION))
+                .expect(&format_error!("decimal overflow when calculating refill"));
+
+            limit
+                .filled_level
+                .checked_add(refill)
+                .expect(&format_error!("decimal overflow when calculating refill"))
+                .min(max_amount)
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::contracts::isms::types::Types;
+use crate::types::metadata::AggregationIsmMetadata;
+use crate::{format_error, panic_error};
+use scrypto::prelude::*;
+
+#[blueprint]
+mod aggregation_ism {
+
+    struct AggregationIsm {
+        modules: Vec<ComponentAddress>,
+        threshold: usize,
+    }
+
+    impl AggregationIsm {
+        pub fn instantiate(
+            modules: Vec<ComponentAddress>,
+            threshold: usize,
+        ) -> Global<AggregationIsm> {
+            if threshold == 0 {
+                panic_error!("threshold must be greater than zero");
+            }
+            if modules.len() < threshold {
+                panic_error!("threshold must be less than or equal to the number of modules");
+            }
+
+            Self { modules, threshold }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize()
+        }
+
+        pub fn module_type(&self) -> Types {
+            Types::Aggregation
+        }
+
+        pub fn modules_and_threshold(&self, _message: Vec<u8>) -> (Vec<ComponentAddress>, usize) {
+            (self.modules.clone(), self.threshold)
+        }
+
+        /// Verifies the message against every module the relayer provided metadata for.
+        /// Modules without metadata are skipped, the message is accepted once `threshold`
+        /// modules have successfully verified it.
+        pub fn verify(&mut self, raw_metadata: Vec<u8>, raw_message: Vec<u8>) -> bool {
+            let metadata: AggregationIsmMetadata = raw_metadata.into();
+
+            let mut verified = 0usize;
+            for (index, ism) in self.modules.iter().enumerate() {
+                let Some(module_metadata) = metadata.metadata_at(index) else {
+                    continue;
+                };
+
+                let result = ScryptoVmV1Api::object_call(
+                    ism.as_node_id(),
+                    "verify",
+                    scrypto_args!(module_metadata, raw_message.clone()),
+                );
+
+                let result: bool = scrypto_decode(&result)
+                    .expect(&format_error!("failed to decode ISM verification result"));
+                if !result {
+                    return false;
+                }
+
+                verified += 1;
+                if verified == self.threshold {
+                    return true;
+                }
+            }
+
+            panic_error!("threshold not reached")
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::contracts::isms::types::Types;
+use crate::format_error;
+use crate::types::{warp_payload::WarpPayload, HyperlaneMessage};
+use scrypto::prelude::*;
+
+#[blueprint]
+mod amount_routing_ism {
+
+    struct AmountRoutingIsm {
+        // ISM for transfers below the threshold
+        lower: ComponentAddress,
+        // ISM for transfers at or above the threshold
+        upper: ComponentAddress,
+        threshold: Decimal,
+        // divisibility of the token amounts in the warp payload
+        divisibility: u32,
+    }
+
+    impl AmountRoutingIsm {
+        /// The divisibility has to match the divisibility of the token that is received.
+        pub fn instantiate(
+            lower: ComponentAddress,
+            upper: ComponentAddress,
+            threshold: Decimal,
+            divisibility: u32,
+        ) -> Global<AmountRoutingIsm> {
+            Self {
+                lower,
+                upper,
+                threshold,
+                divisibility,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        pub fn module_type(&self) -> Types {
+            Types::Routing
+        }
+
+        pub fn lower(&self) -> ComponentAddress {
+            self.lower
+        }
+
+        pub fn upper(&self) -> ComponentAddress {
+            self.upper
+        }
+
+        pub fn threshold(&self) -> Decimal {
+            self.threshold
+        }
+
+        /// Routes a message to the underlying ISM selected by the transfer amount
+        pub fn route(&self, raw_message: Vec<u8>) -> ComponentAddress {
+            let message: HyperlaneMessage = raw_message.into();
+
+            let warp_payload = WarpPayload::try_from(message.body)
+                .expect(&format_error!("failed to decode warp payload"));
+
+            if warp_payload.get_amount(self.divisibility) >= self.threshold {
+                self.upper
+            } else {
+                self.lower
+            }
+        }
+
+        pub fn verify(&mut self, raw_metadata: Vec<u8>, raw_message: Vec<u8>) -> bool {
+            let ism = self.route(raw_message.clone());
+
+            let result = ScryptoVmV1Api::object_call(
+                ism.as_node_id(),
+                "verify",
+                scrypto_args!(raw_metadata, raw_message),
+            );
+
+            scrypto_decode(&result)
+                .expect(&format_error!("failed to decode ISM verification result"))
+        }
+    }
+}
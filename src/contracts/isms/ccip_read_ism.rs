@@ -0,0 +1,122 @@
+use crate::contracts::isms::types::Types;
+use crate::types::metadata::CcipReadIsmMetadata;
+use crate::types::{ccip_read_digest, recover_eth_address, Bytes32, EthAddress, HyperlaneMessage};
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct UrlsSetEvent {
+    pub urls: Vec<String>,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SignersSetEvent {
+    pub signers: Vec<EthAddress>,
+}
+
+#[blueprint]
+#[events(UrlsSetEvent, SignersSetEvent)]
+mod ccip_read_ism {
+
+    enable_method_auth! {
+        methods {
+            // Public
+            module_type => PUBLIC;
+            urls => PUBLIC;
+            signers => PUBLIC;
+            get_offchain_verify_info => PUBLIC;
+            verify => PUBLIC;
+
+            // Owner only
+            set_urls => restrict_to: [OWNER];
+            set_signers => restrict_to: [OWNER];
+        }
+    }
+
+    struct CcipReadIsm {
+        // offchain gateways the relayer queries for the metadata
+        urls: Vec<String>,
+        // gateway keys whose signatures are accepted
+        signers: Vec<EthAddress>,
+    }
+
+    impl CcipReadIsm {
+        pub fn instantiate(
+            urls: Vec<String>,
+            signers: Vec<EthAddress>,
+        ) -> (Global<CcipReadIsm>, FungibleBucket) {
+            // reserve an address for the component
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(CcipReadIsm::blueprint_id());
+
+            // create new owner badge
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .metadata(metadata!(init {
+                    "name" => "CcipRead Ism Owner Badge", locked;
+                    "component" => component_address, locked;
+                }))
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            let component = Self { urls, signers }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
+                    owner_badge.resource_address()
+                ))))
+                .with_address(address_reservation)
+                .globalize();
+
+            (component, owner_badge)
+        }
+
+        pub fn module_type(&self) -> Types {
+            Types::CcipRead
+        }
+
+        pub fn urls(&self) -> Vec<String> {
+            self.urls.clone()
+        }
+
+        pub fn signers(&self) -> Vec<EthAddress> {
+            self.signers.clone()
+        }
+
+        pub fn set_urls(&mut self, urls: Vec<String>) {
+            self.urls = urls.clone();
+
+            Runtime::emit_event(UrlsSetEvent { urls });
+        }
+
+        pub fn set_signers(&mut self, signers: Vec<EthAddress>) {
+            self.signers = signers.clone();
+
+            Runtime::emit_event(SignersSetEvent { signers });
+        }
+
+        /// Returns the gateway URLs and the call data the relayer has to send to them.
+        /// Format of the call data:
+        /// [   0:  32] Address of this ISM
+        /// [  32:????] Raw message
+        ///
+        /// The gateway responds with a signature of "ccip_read_digest()", which is passed
+        /// as metadata to "verify()".
+        pub fn get_offchain_verify_info(&self, raw_message: Vec<u8>) -> (Vec<String>, Vec<u8>) {
+            let ism_address: Bytes32 = Runtime::global_address().into();
+
+            let mut call_data = ism_address.as_ref().to_vec();
+            call_data.extend(raw_message);
+
+            (self.urls.clone(), call_data)
+        }
+
+        pub fn verify(&mut self, metadata: Vec<u8>, raw_message: Vec<u8>) -> bool {
+            let metadata: CcipReadIsmMetadata = metadata.into();
+
+            let message: HyperlaneMessage = raw_message.into();
+            let digest = ccip_read_digest(Runtime::global_address().into(), message.id());
+
+            let signer = recover_eth_address(&digest, &metadata.signature);
+
+            self.signers.contains(&signer)
+        }
+    }
+}
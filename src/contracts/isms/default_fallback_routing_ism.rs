@@ -0,0 +1,129 @@
+use crate::contracts::{isms::types::Types, mailbox::DEFAULT_ISM_METADATA};
+use crate::types::HyperlaneMessage;
+use crate::{format_error, panic_error};
+use scrypto::prelude::*;
+
+#[blueprint]
+mod default_fallback_routing_ism {
+
+    enable_method_auth! {
+        methods {
+            // Public
+            module_type => PUBLIC;
+            verify => PUBLIC;
+            route => PUBLIC;
+            mailbox => PUBLIC;
+            fallback_ism => PUBLIC;
+
+            // Private
+            set_route => restrict_to: [OWNER];
+            remove_route => restrict_to: [OWNER];
+        }
+    }
+
+    struct DefaultFallbackRoutingIsm {
+        routes: KeyValueStore<u32, ComponentAddress>,
+        parent: ComponentAddress,
+    }
+
+    impl DefaultFallbackRoutingIsm {
+        pub fn instantiate(
+            parent: ComponentAddress,
+            initial_routes: Vec<(u32, ComponentAddress)>,
+        ) -> (Global<DefaultFallbackRoutingIsm>, FungibleBucket) {
+            // reserve an address for the component
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(DefaultFallbackRoutingIsm::blueprint_id());
+
+            // create new owner badge
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .metadata(metadata!(init {
+                    "name" => "Default Fallback Routing Ism Owner Badge", locked;
+                    "component" => component_address, locked;
+                }))
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            let routes: KeyValueStore<u32, ComponentAddress> = KeyValueStore::new();
+            for (domain, ism) in initial_routes {
+                routes.insert(domain, ism);
+            }
+
+            let component = Self { routes, parent }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
+                    owner_badge.resource_address()
+                ))))
+                .with_address(address_reservation)
+                .globalize();
+
+            (component, owner_badge)
+        }
+
+        pub fn module_type(&self) -> Types {
+            Types::Routing
+        }
+
+        pub fn mailbox(&self) -> ComponentAddress {
+            self.parent
+        }
+
+        /// Returns the current default ISM of the mailbox
+        pub fn fallback_ism(&self) -> Option<ComponentAddress> {
+            Self::mailbox_default_ism(self.parent)
+        }
+
+        /// Routes a message to the ISM configured for its origin, or to the mailbox default ISM
+        pub fn route(&self, raw_message: Vec<u8>) -> ComponentAddress {
+            let message: HyperlaneMessage = raw_message.into();
+
+            if let Some(ism) = self.routes.get(&message.origin) {
+                return *ism;
+            }
+
+            let fallback_ism = self
+                .fallback_ism()
+                .expect(&format_error!("no ISM for route {}", message.origin));
+            if fallback_ism == Runtime::global_address() {
+                panic_error!("fallback ISM can not be the routing ISM itself");
+            }
+
+            fallback_ism
+        }
+
+        pub fn verify(&mut self, raw_metadata: Vec<u8>, raw_message: Vec<u8>) -> bool {
+            let ism = self.route(raw_message.clone());
+
+            let result = ScryptoVmV1Api::object_call(
+                ism.as_node_id(),
+                "verify",
+                scrypto_args!(raw_metadata, raw_message),
+            );
+
+            scrypto_decode(&result)
+                .expect(&format_error!("failed to decode ISM verification result"))
+        }
+
+        pub fn set_route(&mut self, domain: u32, ism_address: ComponentAddress) {
+            self.routes.insert(domain, ism_address);
+        }
+
+        pub fn remove_route(&mut self, domain: u32) {
+            self.routes.remove(&domain);
+        }
+
+        /// The mailbox state is locked while it processes a message, so the default ISM is
+        /// read from the metadata of the mailbox instead of calling "Mailbox::default_ism()".
+        fn mailbox_default_ism(mailbox: ComponentAddress) -> Option<ComponentAddress> {
+            let mailbox: Global<AnyComponent> = mailbox.into();
+            let default_ism: Option<GlobalAddress> = mailbox
+                .get_metadata(DEFAULT_ISM_METADATA)
+                .expect(&format_error!("failed to decode default_ism metadata"));
+
+            default_ism.map(|address| {
+                ComponentAddress::try_from(address)
+                    .expect(&format_error!("default ISM is not a component"))
+            })
+        }
+    }
+}
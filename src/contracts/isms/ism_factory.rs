@@ -0,0 +1,126 @@
+use crate::contracts::isms::multisig_ism::assert_valid_validator_set;
+use crate::contracts::isms::types::Types;
+use crate::types::{Bytes32, EthAddress};
+use crate::{format_error, panic_error};
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct IsmDeployedEvent {
+    pub hash: Bytes32,
+    pub module_type: Types,
+    pub ism: ComponentAddress,
+    pub validators: Vec<EthAddress>,
+    pub threshold: usize,
+}
+
+#[blueprint]
+#[events(IsmDeployedEvent)]
+mod ism_factory {
+
+    struct IsmFactory {
+        // map from hash of the ISM configuration -> deployed ISM
+        deployments: KeyValueStore<Bytes32, ComponentAddress>,
+    }
+
+    impl IsmFactory {
+        pub fn instantiate() -> Global<IsmFactory> {
+            Self {
+                deployments: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        /// Returns the multisig ISM for the given configuration and deploys it, if it doesn't
+        /// exist yet. Deployed ISMs have no owner, so the same ISM can safely be shared by
+        /// every application with the same validator set.
+        pub fn deploy(
+            &mut self,
+            module_type: Types,
+            validators: Vec<EthAddress>,
+            threshold: usize,
+        ) -> ComponentAddress {
+            let blueprint_name = Self::blueprint_name(&module_type);
+            if validators.is_empty() {
+                panic_error!("at least one validator is required");
+            }
+
+            let validators = Self::sorted(validators);
+            assert_valid_validator_set(&validators, threshold);
+
+            let hash = Self::hash(blueprint_name, &validators, threshold);
+            if let Some(ism) = self.deployments.get(&hash) {
+                return *ism;
+            }
+
+            let result = ScryptoVmV1Api::blueprint_call(
+                Runtime::package_address(),
+                blueprint_name,
+                "instantiate",
+                scrypto_args!(validators.clone(), threshold),
+            );
+            let ism: ComponentAddress =
+                scrypto_decode(&result).expect(&format_error!("failed to decode deployed ISM"));
+
+            self.deployments.insert(hash, ism);
+
+            Runtime::emit_event(IsmDeployedEvent {
+                hash,
+                module_type,
+                ism,
+                validators,
+                threshold,
+            });
+
+            ism
+        }
+
+        pub fn deployed_ism(
+            &self,
+            module_type: Types,
+            validators: Vec<EthAddress>,
+            threshold: usize,
+        ) -> Option<ComponentAddress> {
+            let hash = self.ism_hash(module_type, validators, threshold);
+            self.deployments.get(&hash).map(|ism| *ism)
+        }
+
+        /// Format of the hashed configuration:
+        /// [   0:????] Blueprint name of the ISM type
+        /// [????:????] Validators, sorted in ascending order (length := 20 * validators)
+        /// [????:????] Threshold (8 bytes)
+        pub fn ism_hash(
+            &self,
+            module_type: Types,
+            validators: Vec<EthAddress>,
+            threshold: usize,
+        ) -> Bytes32 {
+            let blueprint_name = Self::blueprint_name(&module_type);
+            Self::hash(blueprint_name, &Self::sorted(validators), threshold)
+        }
+
+        fn blueprint_name(module_type: &Types) -> &'static str {
+            match module_type {
+                Types::MessageIdMultisig => "MessageIdMultisigIsm",
+                Types::MerkleRootMultisig => "MerkleRootMultisigIsm",
+                _ => panic_error!("only multisig ISMs can be deployed"),
+            }
+        }
+
+        fn sorted(mut validators: Vec<EthAddress>) -> Vec<EthAddress> {
+            validators.sort();
+            validators
+        }
+
+        fn hash(blueprint_name: &str, validators: &[EthAddress], threshold: usize) -> Bytes32 {
+            let mut bytes = blueprint_name.as_bytes().to_vec();
+            for validator in validators {
+                bytes.extend(validator.as_ref());
+            }
+            bytes.extend((threshold as u64).to_be_bytes());
+
+            keccak256_hash(bytes).into()
+        }
+    }
+}
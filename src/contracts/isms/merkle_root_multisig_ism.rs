@@ -1,17 +1,74 @@
-use crate::contracts::isms::{multisig_ism::verify_multisig, types::Types};
+use crate::contracts::isms::{
+    multisig_ism::{
+        assert_valid_validator_set, validator_public_keys, verify_multisig,
+        verify_multisig_with_public_keys, ValidatorPublicKeysSet, ValidatorSetUpdated,
+    },
+    types::Types,
+};
 use crate::panic_error;
 use crate::types::merkle::merkle_root_from_branch;
 use crate::types::metadata::MultisigIsmMerkleRootMetadata;
 use crate::types::EthAddress;
-use crate::types::HyperlaneMessage;
+use crate::types::{Bytes32, HyperlaneMessage};
 use scrypto::prelude::*;
 
+/// (origin domain, origin merkle tree hook, checkpoint root, checkpoint index)
+pub type CheckpointKey = (u32, Bytes32, Bytes32, u32);
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct CheckpointCachedEvent {
+    pub origin: u32,
+    pub origin_merkle_tree_hook: Bytes32,
+    pub root: Bytes32,
+    pub index: u32,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct CheckpointEvictedEvent {
+    pub origin: u32,
+    pub origin_merkle_tree_hook: Bytes32,
+    pub root: Bytes32,
+    pub index: u32,
+}
+
 #[blueprint]
+#[events(
+    ValidatorSetUpdated,
+    ValidatorPublicKeysSet,
+    CheckpointCachedEvent,
+    CheckpointEvictedEvent
+)]
 mod merkle_root_multisig_ism {
 
+    enable_method_auth! {
+        methods {
+            // Public
+            module_type => PUBLIC;
+            validators_and_threshold => PUBLIC;
+            validator_public_keys => PUBLIC;
+            checkpoint_caching => PUBLIC;
+            checkpoint_cached => PUBLIC;
+            verify => PUBLIC;
+
+            // Owner only
+            set_validators_and_threshold => restrict_to: [OWNER];
+            set_validator_public_keys => restrict_to: [OWNER];
+            set_checkpoint_caching => restrict_to: [OWNER];
+            evict_checkpoint => restrict_to: [OWNER];
+        }
+    }
+
     struct MerkleRootMultisigIsm {
         validators: Vec<EthAddress>,
         threshold: usize,
+        // compressed keys of the validators, verifies signatures without deriving addresses
+        validator_keys: Option<Vec<Secp256k1PublicKey>>,
+        // incremented on every validator set update, invalidates all cached checkpoints
+        validator_set_version: u32,
+        // if enabled, checkpoints that reached the threshold are cached
+        checkpoint_caching: bool,
+        // map from checkpoint -> validator set version that verified it
+        verified_checkpoints: KeyValueStore<CheckpointKey, u32>,
     }
 
     impl MerkleRootMultisigIsm {
@@ -26,12 +83,77 @@ mod merkle_root_multisig_ism {
             Self {
                 validators,
                 threshold,
+                validator_keys: None,
+                validator_set_version: 0,
+                checkpoint_caching: false,
+                verified_checkpoints: KeyValueStore::new(),
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::None)
             .globalize()
         }
 
+        /// Instantiates an ISM without owner that verifies signatures against the given
+        /// uncompressed public keys of the validators, ordered like the validators.
+        pub fn instantiate_with_public_keys(
+            validators: Vec<EthAddress>,
+            threshold: usize,
+            public_keys: Vec<Secp256k1UncompressedPublicKey>,
+        ) -> Global<MerkleRootMultisigIsm> {
+            assert_valid_validator_set(&validators, threshold);
+            let validator_keys = validator_public_keys(&validators, &public_keys);
+
+            Self {
+                validators,
+                threshold,
+                validator_keys: Some(validator_keys),
+                validator_set_version: 0,
+                checkpoint_caching: false,
+                verified_checkpoints: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        /// Instantiates an ISM whose validator set can be rotated by the holder of the owner badge.
+        pub fn instantiate_with_owner(
+            validators: Vec<EthAddress>,
+            threshold: usize,
+        ) -> (Global<MerkleRootMultisigIsm>, FungibleBucket) {
+            assert_valid_validator_set(&validators, threshold);
+
+            // reserve an address for the component
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(MerkleRootMultisigIsm::blueprint_id());
+
+            // create new owner badge
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .metadata(metadata!(init {
+                    "name" => "MerkleRoot Multisig Ism Owner Badge", locked;
+                    "component" => component_address, locked;
+                }))
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            let component = Self {
+                validators,
+                threshold,
+                validator_keys: None,
+                validator_set_version: 0,
+                checkpoint_caching: false,
+                verified_checkpoints: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
+                owner_badge.resource_address()
+            ))))
+            .with_address(address_reservation)
+            .globalize();
+
+            (component, owner_badge)
+        }
+
         pub fn module_type(&self) -> Types {
             Types::MerkleRootMultisig
         }
@@ -40,6 +162,89 @@ mod merkle_root_multisig_ism {
             (self.validators.clone(), self.threshold)
         }
 
+        pub fn validator_public_keys(&self) -> Option<Vec<Secp256k1PublicKey>> {
+            self.validator_keys.clone()
+        }
+
+        /// Configures the uncompressed public keys of the validators, ordered like the
+        /// validators. Recovered signer keys are then compared with these keys instead of deriving
+        /// the ethereum address of every signer, see "verify_multisig_with_public_keys()".
+        /// Updating the validator set removes the keys again.
+        pub fn set_validator_public_keys(
+            &mut self,
+            public_keys: Vec<Secp256k1UncompressedPublicKey>,
+        ) {
+            let public_keys = validator_public_keys(&self.validators, &public_keys);
+            self.validator_keys = Some(public_keys.clone());
+
+            Runtime::emit_event(ValidatorPublicKeysSet { public_keys });
+        }
+
+        pub fn set_validators_and_threshold(
+            &mut self,
+            validators: Vec<EthAddress>,
+            threshold: usize,
+        ) {
+            assert_valid_validator_set(&validators, threshold);
+
+            self.validators = validators.clone();
+            self.threshold = threshold;
+            // the keys belong to the previous validator set
+            self.validator_keys = None;
+            self.validator_set_version += 1;
+
+            Runtime::emit_event(ValidatorSetUpdated {
+                validators,
+                threshold,
+            });
+        }
+
+        pub fn checkpoint_caching(&self) -> bool {
+            self.checkpoint_caching
+        }
+
+        /// Returns whether the checkpoint has been verified by the current validator set.
+        /// Checkpoints are keyed by the origin domain as well, since the merkle tree hook
+        /// address alone is not unique across chains.
+        pub fn checkpoint_cached(
+            &self,
+            origin: u32,
+            origin_merkle_tree_hook: Bytes32,
+            root: Bytes32,
+            index: u32,
+        ) -> bool {
+            self.verified_checkpoints
+                .get(&(origin, origin_merkle_tree_hook, root, index))
+                .is_some_and(|version| *version == self.validator_set_version)
+        }
+
+        /// Enables or disables the checkpoint cache. Disabling the cache ignores all
+        /// cached checkpoints until it is enabled again.
+        pub fn set_checkpoint_caching(&mut self, enabled: bool) {
+            self.checkpoint_caching = enabled;
+        }
+
+        pub fn evict_checkpoint(
+            &mut self,
+            origin: u32,
+            origin_merkle_tree_hook: Bytes32,
+            root: Bytes32,
+            index: u32,
+        ) {
+            self.verified_checkpoints
+                .remove(&(origin, origin_merkle_tree_hook, root, index));
+
+            Runtime::emit_event(CheckpointEvictedEvent {
+                origin,
+                origin_merkle_tree_hook,
+                root,
+                index,
+            });
+        }
+
+        /// If checkpoint caching is enabled, messages proven against a checkpoint that has
+        /// already reached the threshold only need a valid merkle branch. The signatures
+        /// are not recovered again.
         pub fn verify(&mut self, metadata: Vec<u8>, message: Vec<u8>) -> bool {
             let metadata: MultisigIsmMerkleRootMetadata = metadata.into();
 
@@ -56,12 +261,45 @@ mod merkle_root_multisig_ism {
                 metadata.signed_checkpoint_index,
             );
 
-            verify_multisig(
-                digest,
-                &metadata.validator_signatures,
-                &self.validators,
-                self.threshold,
-            )
+            if !self.checkpoint_caching {
+                return self.verify_signatures(digest, &metadata.validator_signatures);
+            }
+
+            let origin = message.origin;
+            let origin_merkle_tree_hook = metadata.origin_merkle_tree_hook;
+            let root: Bytes32 = signed_root.into();
+            let index = metadata.signed_checkpoint_index;
+            if self.checkpoint_cached(origin, origin_merkle_tree_hook, root, index) {
+                return true;
+            }
+
+            // panics if the threshold is not reached
+            self.verify_signatures(digest, &metadata.validator_signatures);
+
+            self.verified_checkpoints.insert(
+                (origin, origin_merkle_tree_hook, root, index),
+                self.validator_set_version,
+            );
+            Runtime::emit_event(CheckpointCachedEvent {
+                origin,
+                origin_merkle_tree_hook,
+                root,
+                index,
+            });
+
+            true
+        }
+
+        fn verify_signatures(&self, digest: Hash, signatures: &[Secp256k1Signature]) -> bool {
+            match &self.validator_keys {
+                Some(validator_keys) => verify_multisig_with_public_keys(
+                    digest,
+                    signatures,
+                    validator_keys,
+                    self.threshold,
+                ),
+                None => verify_multisig(digest, signatures, &self.validators, self.threshold),
+            }
         }
     }
 }
@@ -1,4 +1,7 @@
-use crate::contracts::isms::multisig_ism::verify_multisig;
+use crate::contracts::isms::multisig_ism::{
+    assert_valid_validator_set, validator_public_keys, verify_multisig,
+    verify_multisig_with_public_keys, ValidatorPublicKeysSet, ValidatorSetUpdated,
+};
 use crate::contracts::isms::types::Types;
 use crate::panic_error;
 use crate::types::metadata::MultisigIsmMessageIdMetadata;
@@ -7,11 +10,28 @@ use crate::types::HyperlaneMessage;
 use scrypto::prelude::*;
 
 #[blueprint]
+#[events(ValidatorSetUpdated, ValidatorPublicKeysSet)]
 mod message_id_multisig_ism {
 
+    enable_method_auth! {
+        methods {
+            // Public
+            module_type => PUBLIC;
+            validators_and_threshold => PUBLIC;
+            validator_public_keys => PUBLIC;
+            verify => PUBLIC;
+
+            // Owner only
+            set_validators_and_threshold => restrict_to: [OWNER];
+            set_validator_public_keys => restrict_to: [OWNER];
+        }
+    }
+
     struct MessageIdMultisigIsm {
         validators: Vec<EthAddress>,
         threshold: usize,
+        // compressed keys of the validators, verifies signatures without deriving addresses
+        validator_keys: Option<Vec<Secp256k1PublicKey>>,
     }
 
     impl MessageIdMultisigIsm {
@@ -25,12 +45,68 @@ mod message_id_multisig_ism {
             Self {
                 validators,
                 threshold,
+                validator_keys: None,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::None)
             .globalize()
         }
 
+        /// Instantiates an ISM without owner that verifies signatures against the given
+        /// uncompressed public keys of the validators, ordered like the validators.
+        pub fn instantiate_with_public_keys(
+            validators: Vec<EthAddress>,
+            threshold: usize,
+            public_keys: Vec<Secp256k1UncompressedPublicKey>,
+        ) -> Global<MessageIdMultisigIsm> {
+            assert_valid_validator_set(&validators, threshold);
+            let validator_keys = validator_public_keys(&validators, &public_keys);
+
+            Self {
+                validators,
+                threshold,
+                validator_keys: Some(validator_keys),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        /// Instantiates an ISM whose validator set can be rotated by the holder of the owner badge.
+        pub fn instantiate_with_owner(
+            validators: Vec<EthAddress>,
+            threshold: usize,
+        ) -> (Global<MessageIdMultisigIsm>, FungibleBucket) {
+            assert_valid_validator_set(&validators, threshold);
+
+            // reserve an address for the component
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(MessageIdMultisigIsm::blueprint_id());
+
+            // create new owner badge
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .metadata(metadata!(init {
+                    "name" => "MessageId Multisig Ism Owner Badge", locked;
+                    "component" => component_address, locked;
+                }))
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            let component = Self {
+                validators,
+                threshold,
+                validator_keys: None,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
+                owner_badge.resource_address()
+            ))))
+            .with_address(address_reservation)
+            .globalize();
+
+            (component, owner_badge)
+        }
+
         pub fn module_type(&self) -> Types {
             Types::MessageIdMultisig
         }
@@ -39,6 +115,42 @@ mod message_id_multisig_ism {
             (self.validators.clone(), self.threshold)
         }
 
+        pub fn validator_public_keys(&self) -> Option<Vec<Secp256k1PublicKey>> {
+            self.validator_keys.clone()
+        }
+
+        /// Configures the uncompressed public keys of the validators, ordered like the
+        /// validators. Recovered signer keys are then compared with these keys instead of deriving
+        /// the ethereum address of every signer, see "verify_multisig_with_public_keys()".
+        /// Updating the validator set removes the keys again.
+        pub fn set_validator_public_keys(
+            &mut self,
+            public_keys: Vec<Secp256k1UncompressedPublicKey>,
+        ) {
+            let public_keys = validator_public_keys(&self.validators, &public_keys);
+            self.validator_keys = Some(public_keys.clone());
+
+            Runtime::emit_event(ValidatorPublicKeysSet { public_keys });
+        }
+
+        pub fn set_validators_and_threshold(
+            &mut self,
+            validators: Vec<EthAddress>,
+            threshold: usize,
+        ) {
+            assert_valid_validator_set(&validators, threshold);
+
+            self.validators = validators.clone();
+            self.threshold = threshold;
+            // the keys belong to the previous validator set
+            self.validator_keys = None;
+
+            Runtime::emit_event(ValidatorSetUpdated {
+                validators,
+                threshold,
+            });
+        }
+
         pub fn verify(&mut self, metadata: Vec<u8>, message: Vec<u8>) -> bool {
             let metadata: MultisigIsmMessageIdMetadata = metadata.into();
 
@@ -49,12 +161,19 @@ mod message_id_multisig_ism {
                 metadata.merkle_index,
             );
 
-            verify_multisig(
-                digest,
-                &metadata.validator_signatures,
-                &self.validators,
-                self.threshold,
-            )
+            self.verify_signatures(digest, &metadata.validator_signatures)
+        }
+
+        fn verify_signatures(&self, digest: Hash, signatures: &[Secp256k1Signature]) -> bool {
+            match &self.validator_keys {
+                Some(validator_keys) => verify_multisig_with_public_keys(
+                    digest,
+                    signatures,
+                    validator_keys,
+                    self.threshold,
+                ),
+                None => verify_multisig(digest, signatures, &self.validators, self.threshold),
+            }
         }
     }
 }
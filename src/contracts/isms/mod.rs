@@ -1,6 +1,16 @@
+pub mod aggregation_ism;
+pub mod amount_routing_ism;
+pub mod ccip_read_ism;
+pub mod default_fallback_routing_ism;
+pub mod ism_factory;
 pub mod merkle_root_multisig_ism;
 pub mod message_id_multisig_ism;
 pub mod multisig_ism;
 pub mod noop_ism;
+pub mod optimistic_ism;
+pub mod pausable_ism;
 pub mod routing_ism;
+pub mod trusted_relayer_ism;
 pub mod types;
+pub mod weighted_merkle_root_multisig_ism;
+pub mod weighted_message_id_multisig_ism;
@@ -0,0 +1,210 @@
+use crate::contracts::isms::types::Types;
+use crate::types::{Bytes32, HyperlaneMessage};
+use crate::{format_error, panic_error};
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, Clone)]
+pub struct PreVerification {
+    // submodule that verified the message
+    pub submodule: ComponentAddress,
+    // unix timestamp in seconds
+    pub timestamp: i64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct PreVerifiedEvent {
+    pub message_id: Bytes32,
+    pub submodule: ComponentAddress,
+    pub timestamp: i64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SubmoduleFlaggedEvent {
+    pub submodule: ComponentAddress,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SubmoduleSetEvent {
+    pub submodule: ComponentAddress,
+}
+
+#[blueprint]
+#[events(PreVerifiedEvent, SubmoduleFlaggedEvent, SubmoduleSetEvent)]
+mod optimistic_ism {
+
+    enable_method_auth! {
+        roles {
+            watcher => updatable_by: [OWNER];
+        },
+        methods {
+            // Public
+            module_type => PUBLIC;
+            submodule => PUBLIC;
+            fraud_window => PUBLIC;
+            flagged => PUBLIC;
+            pre_verification => PUBLIC;
+            pre_verify => PUBLIC;
+            verify => PUBLIC;
+
+            // Watcher only
+            flag_submodule => restrict_to: [watcher];
+
+            // Owner only
+            set_submodule => restrict_to: [OWNER];
+        }
+    }
+
+    struct OptimisticIsm {
+        // ISM that verifies messages in "pre_verify()"
+        submodule: ComponentAddress,
+        // seconds that have to pass between pre-verification and verification
+        fraud_window: i64,
+        // submodules that a watcher has flagged as compromised
+        flagged_submodules: KeyValueStore<ComponentAddress, ()>,
+        // map from message id -> pre-verification
+        pre_verifications: KeyValueStore<Bytes32, PreVerification>,
+    }
+
+    impl OptimisticIsm {
+        /// Any of the watcher badges can flag a submodule as compromised.
+        pub fn instantiate(
+            submodule: ComponentAddress,
+            fraud_window: i64,
+            watcher_badges: Vec<ResourceAddress>,
+        ) -> (Global<OptimisticIsm>, FungibleBucket) {
+            if fraud_window < 0 {
+                panic_error!("fraud window can not be negative");
+            }
+
+            // reserve an address for the component
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(OptimisticIsm::blueprint_id());
+
+            // create new owner badge
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .metadata(metadata!(init {
+                    "name" => "Optimistic Ism Owner Badge", locked;
+                    "component" => component_address, locked;
+                }))
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            let component = Self {
+                submodule,
+                fraud_window,
+                flagged_submodules: KeyValueStore::new(),
+                pre_verifications: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
+                owner_badge.resource_address()
+            ))))
+            .roles(roles! {
+                watcher => rule!(require_any_of(watcher_badges));
+            })
+            .with_address(address_reservation)
+            .globalize();
+
+            (component, owner_badge)
+        }
+
+        pub fn module_type(&self) -> Types {
+            Types::Null
+        }
+
+        pub fn submodule(&self) -> ComponentAddress {
+            self.submodule
+        }
+
+        pub fn fraud_window(&self) -> i64 {
+            self.fraud_window
+        }
+
+        pub fn flagged(&self, submodule: ComponentAddress) -> bool {
+            self.flagged_submodules.get(&submodule).is_some()
+        }
+
+        pub fn pre_verification(&self, message_id: Bytes32) -> Option<PreVerification> {
+            self.pre_verifications.get(&message_id).map(|x| x.clone())
+        }
+
+        pub fn set_submodule(&mut self, submodule: ComponentAddress) {
+            self.submodule = submodule;
+
+            Runtime::emit_event(SubmoduleSetEvent { submodule });
+        }
+
+        /// Flagging a submodule blocks the verification of all messages it pre-verified,
+        /// until they are pre-verified again by the submodule that replaces it.
+        pub fn flag_submodule(&mut self, submodule: ComponentAddress) {
+            self.flagged_submodules.insert(submodule, ());
+
+            Runtime::emit_event(SubmoduleFlaggedEvent { submodule });
+        }
+
+        /// Verifies the message with the current submodule and starts the fraud window.
+        /// Messages pre-verified by a flagged submodule can be pre-verified again, which
+        /// replaces the old pre-verification and restarts the fraud window.
+        pub fn pre_verify(&mut self, metadata: Vec<u8>, raw_message: Vec<u8>) {
+            let message: HyperlaneMessage = raw_message.clone().into();
+            let message_id = message.id();
+
+            if let Some(pre_verification) = self.pre_verification(message_id) {
+                if !self.flagged(pre_verification.submodule) {
+                    panic_error!("message already pre-verified");
+                }
+            }
+
+            if self.flagged(self.submodule) {
+                panic_error!("submodule has been flagged as fraudulent");
+            }
+
+            let result = ScryptoVmV1Api::object_call(
+                self.submodule.as_node_id(),
+                "verify",
+                scrypto_args!(metadata, raw_message),
+            );
+            let result: bool = scrypto_decode(&result)
+                .expect(&format_error!("failed to decode ISM verification result"));
+            if !result {
+                panic_error!("submodule verification failed");
+            }
+
+            let timestamp = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+            self.pre_verifications.insert(
+                message_id,
+                PreVerification {
+                    submodule: self.submodule,
+                    timestamp,
+                },
+            );
+
+            Runtime::emit_event(PreVerifiedEvent {
+                message_id,
+                submodule: self.submodule,
+                timestamp,
+            });
+        }
+
+        /// Accepts a pre-verified message once the fraud window has passed, unless the
+        /// submodule that pre-verified it has been flagged. Metadata is ignored.
+        pub fn verify(&mut self, _metadata: Vec<u8>, raw_message: Vec<u8>) -> bool {
+            let message: HyperlaneMessage = raw_message.into();
+
+            let pre_verification = self
+                .pre_verification(message.id())
+                .expect(&format_error!("message has not been pre-verified"));
+
+            if self.flagged(pre_verification.submodule) {
+                panic_error!("submodule has been flagged as fraudulent");
+            }
+
+            let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+            if now < pre_verification.timestamp + self.fraud_window {
+                panic_error!("fraud window has not passed");
+            }
+
+            true
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::contracts::isms::types::Types;
+use crate::panic_error;
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct PausedEvent {}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct UnpausedEvent {}
+
+#[blueprint]
+#[events(PausedEvent, UnpausedEvent)]
+mod pausable_ism {
+
+    enable_method_auth! {
+        roles {
+            pauser => updatable_by: [OWNER];
+        },
+        methods {
+            // Public
+            module_type => PUBLIC;
+            paused => PUBLIC;
+            verify => PUBLIC;
+
+            // Pauser or owner
+            pause => restrict_to: [pauser, OWNER];
+
+            // Owner only
+            unpause => restrict_to: [OWNER];
+        }
+    }
+
+    struct PausableIsm {
+        paused: bool,
+    }
+
+    impl PausableIsm {
+        /// The pauser badge can only pause the ISM, unpausing is reserved for the owner.
+        pub fn instantiate(pauser_badge: ResourceAddress) -> (Global<PausableIsm>, FungibleBucket) {
+            // reserve an address for the component
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(PausableIsm::blueprint_id());
+
+            // create new owner badge
+            let owner_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .metadata(metadata!(init {
+                    "name" => "Pausable Ism Owner Badge", locked;
+                    "component" => component_address, locked;
+                }))
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            let component = Self { paused: false }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
+                    owner_badge.resource_address()
+                ))))
+                .roles(roles! {
+                    pauser => rule!(require(pauser_badge));
+                })
+                .with_address(address_reservation)
+                .globalize();
+
+            (component, owner_badge)
+        }
+
+        pub fn module_type(&self) -> Types {
+            Types::Null
+        }
+
+        pub fn paused(&self) -> bool {
+            self.paused
+        }
+
+        pub fn pause(&mut self) {
+            if self.paused {
+                panic_error!("already paused");
+            }
+            self.paused = true;
+
+            Runtime::emit_event(PausedEvent {});
+        }
+
+        pub fn unpause(&mut self) {
+            if !self.paused {
+                panic_error!("not paused");
+            }
+            self.paused = false;
+
+            Runtime::emit_event(UnpausedEvent {});
+        }
+
+        /// Accepts every message unless the ISM is paused. Metadata is ignored.
+        pub fn verify(&mut self, _metadata: Vec<u8>, _message: Vec<u8>) -> bool {
+            !self.paused
+        }
+    }
+}
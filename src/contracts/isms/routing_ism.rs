@@ -1,9 +1,56 @@
 use crate::contracts::isms::types::Types;
 use crate::format_error;
-use crate::types::HyperlaneMessage;
+use crate::types::{Bytes32, HyperlaneMessage};
 use scrypto::prelude::*;
 
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct RouteSet {
+    pub domain: u32,
+    pub ism: ComponentAddress,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct RouteRemoved {
+    pub domain: u32,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SenderRouteSet {
+    pub origin: u32,
+    pub sender: Bytes32,
+    pub ism: ComponentAddress,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SenderRouteRemoved {
+    pub origin: u32,
+    pub sender: Bytes32,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct RecipientRouteSet {
+    pub origin: u32,
+    pub sender: Bytes32,
+    pub recipient: Bytes32,
+    pub ism: ComponentAddress,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct RecipientRouteRemoved {
+    pub origin: u32,
+    pub sender: Bytes32,
+    pub recipient: Bytes32,
+}
+
 #[blueprint]
+#[events(
+    RouteSet,
+    RouteRemoved,
+    SenderRouteSet,
+    SenderRouteRemoved,
+    RecipientRouteSet,
+    RecipientRouteRemoved
+)]
 mod routing_ism {
 
     enable_method_auth! {
@@ -12,15 +59,36 @@ mod routing_ism {
             module_type => PUBLIC;
             verify => PUBLIC;
             route => PUBLIC;
+            domains => PUBLIC;
+            routes => PUBLIC;
+            sender_route => PUBLIC;
+            sender_routes => PUBLIC;
+            recipient_route => PUBLIC;
+            recipient_routes => PUBLIC;
 
             // Private
             set_route => restrict_to: [OWNER];
+            set_routes => restrict_to: [OWNER];
             remove_route => restrict_to: [OWNER];
+            set_sender_route => restrict_to: [OWNER];
+            remove_sender_route => restrict_to: [OWNER];
+            set_recipient_route => restrict_to: [OWNER];
+            remove_recipient_route => restrict_to: [OWNER];
         }
     }
 
     struct RoutingIsm {
         routes: KeyValueStore<u32, ComponentAddress>,
+        // configured domains in ascending order, the routes can't be enumerated
+        domains: Vec<u32>,
+        // routes for single remote applications, take precedence over the origin routes
+        sender_routes: KeyValueStore<(u32, Bytes32), ComponentAddress>,
+        // configured (origin, sender) pairs in ascending order
+        senders: Vec<(u32, Bytes32)>,
+        // routes for single local applications, take precedence over the sender routes
+        recipient_routes: KeyValueStore<(u32, Bytes32, Bytes32), ComponentAddress>,
+        // configured (origin, sender, recipient) triples in ascending order
+        recipients: Vec<(u32, Bytes32, Bytes32)>,
     }
 
     impl RoutingIsm {
@@ -40,12 +108,19 @@ mod routing_ism {
                 .divisibility(DIVISIBILITY_NONE)
                 .mint_initial_supply(1);
 
-            let routes: KeyValueStore<u32, ComponentAddress> = KeyValueStore::new();
+            let mut routing_ism = Self {
+                routes: KeyValueStore::new(),
+                domains: Vec::new(),
+                sender_routes: KeyValueStore::new(),
+                senders: Vec::new(),
+                recipient_routes: KeyValueStore::new(),
+                recipients: Vec::new(),
+            };
             for (domain, ism) in initial_routes {
-                routes.insert(domain, ism);
+                routing_ism.insert_route(domain, ism);
             }
 
-            let component = Self { routes }
+            let component = routing_ism
                 .instantiate()
                 .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
                     owner_badge.resource_address()
@@ -60,10 +135,22 @@ mod routing_ism {
             Types::Routing
         }
 
-        /// Routes a message to a underlying ISM
+        /// Routes a message to the ISM configured for its (origin, sender, recipient) triple,
+        /// then to the ISM configured for its (origin, sender) pair,
+        /// or to the ISM configured for its origin
         pub fn route(&self, raw_message: Vec<u8>) -> ComponentAddress {
             let message: HyperlaneMessage = raw_message.clone().into();
 
+            if let Some(ism) =
+                self.recipient_route(message.origin, message.sender, message.recipient)
+            {
+                return ism;
+            }
+
+            if let Some(ism) = self.sender_route(message.origin, message.sender) {
+                return ism;
+            }
+
             let ism = self
                 .routes
                 .get(&message.origin)
@@ -72,6 +159,46 @@ mod routing_ism {
             *ism
         }
 
+        pub fn sender_route(&self, origin: u32, sender: Bytes32) -> Option<ComponentAddress> {
+            self.sender_routes.get(&(origin, sender)).map(|ism| *ism)
+        }
+
+        /// Returns all sender routes, ordered by origin and sender
+        pub fn sender_routes(&self) -> Vec<(u32, Bytes32, ComponentAddress)> {
+            self.senders
+                .iter()
+                .map(|(origin, sender)| {
+                    let ism = *self.sender_routes.get(&(*origin, *sender)).unwrap();
+                    (*origin, *sender, ism)
+                })
+                .collect()
+        }
+
+        pub fn recipient_route(
+            &self,
+            origin: u32,
+            sender: Bytes32,
+            recipient: Bytes32,
+        ) -> Option<ComponentAddress> {
+            self.recipient_routes
+                .get(&(origin, sender, recipient))
+                .map(|ism| *ism)
+        }
+
+        /// Returns all recipient routes, ordered by origin, sender and recipient
+        pub fn recipient_routes(&self) -> Vec<(u32, Bytes32, Bytes32, ComponentAddress)> {
+            self.recipients
+                .iter()
+                .map(|(origin, sender, recipient)| {
+                    let ism = *self
+                        .recipient_routes
+                        .get(&(*origin, *sender, *recipient))
+                        .unwrap();
+                    (*origin, *sender, *recipient, ism)
+                })
+                .collect()
+        }
+
         pub fn verify(&mut self, raw_metadata: Vec<u8>, raw_message: Vec<u8>) -> bool {
             let ism = self.route(raw_message.clone());
 
@@ -85,12 +212,124 @@ mod routing_ism {
                 .expect(&format_error!("failed to decode ISM verification result"))
         }
 
+        pub fn domains(&self) -> Vec<u32> {
+            self.domains.clone()
+        }
+
+        /// Returns all routes, ordered by domain
+        pub fn routes(&self) -> Vec<(u32, ComponentAddress)> {
+            self.domains
+                .iter()
+                .map(|domain| (*domain, *self.routes.get(domain).unwrap()))
+                .collect()
+        }
+
         pub fn set_route(&mut self, domain: u32, ism_address: ComponentAddress) {
-            self.routes.insert(domain, ism_address);
+            self.insert_route(domain, ism_address);
+
+            Runtime::emit_event(RouteSet {
+                domain,
+                ism: ism_address,
+            });
+        }
+
+        /// Sets multiple routes at once. Routes of other domains are kept.
+        pub fn set_routes(&mut self, routes: Vec<(u32, ComponentAddress)>) {
+            for (domain, ism_address) in routes {
+                self.set_route(domain, ism_address);
+            }
         }
 
         pub fn remove_route(&mut self, domain: u32) {
-            self.routes.remove(&domain);
+            if self.routes.remove(&domain).is_none() {
+                return;
+            }
+
+            if let Ok(index) = self.domains.binary_search(&domain) {
+                self.domains.remove(index);
+            }
+
+            Runtime::emit_event(RouteRemoved { domain });
+        }
+
+        pub fn set_sender_route(
+            &mut self,
+            origin: u32,
+            sender: Bytes32,
+            ism_address: ComponentAddress,
+        ) {
+            self.sender_routes.insert((origin, sender), ism_address);
+
+            if let Err(index) = self.senders.binary_search(&(origin, sender)) {
+                self.senders.insert(index, (origin, sender));
+            }
+
+            Runtime::emit_event(SenderRouteSet {
+                origin,
+                sender,
+                ism: ism_address,
+            });
+        }
+
+        /// Messages of the sender fall back to the route of their origin
+        pub fn remove_sender_route(&mut self, origin: u32, sender: Bytes32) {
+            if self.sender_routes.remove(&(origin, sender)).is_none() {
+                return;
+            }
+
+            if let Ok(index) = self.senders.binary_search(&(origin, sender)) {
+                self.senders.remove(index);
+            }
+
+            Runtime::emit_event(SenderRouteRemoved { origin, sender });
+        }
+
+        pub fn set_recipient_route(
+            &mut self,
+            origin: u32,
+            sender: Bytes32,
+            recipient: Bytes32,
+            ism_address: ComponentAddress,
+        ) {
+            let key = (origin, sender, recipient);
+            self.recipient_routes.insert(key, ism_address);
+
+            if let Err(index) = self.recipients.binary_search(&key) {
+                self.recipients.insert(index, key);
+            }
+
+            Runtime::emit_event(RecipientRouteSet {
+                origin,
+                sender,
+                recipient,
+                ism: ism_address,
+            });
+        }
+
+        /// Messages to the recipient fall back to the route of their sender or origin
+        pub fn remove_recipient_route(&mut self, origin: u32, sender: Bytes32, recipient: Bytes32) {
+            let key = (origin, sender, recipient);
+            if self.recipient_routes.remove(&key).is_none() {
+                return;
+            }
+
+            if let Ok(index) = self.recipients.binary_search(&key) {
+                self.recipients.remove(index);
+            }
+
+            Runtime::emit_event(RecipientRouteRemoved {
+                origin,
+                sender,
+                recipient,
+            });
+        }
+
+        fn insert_route(&mut self, domain: u32, ism_address: ComponentAddress) {
+            self.routes.insert(domain, ism_address);
+
+            if let Err(index) = self.domains.binary_search(&domain) {
+                self.domains.insert(index, domain);
+            }
         }
     }
 }
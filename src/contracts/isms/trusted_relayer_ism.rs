@@ -0,0 +1,76 @@
+use crate::contracts::isms::types::Types;
+use crate::types::{Bytes32, HyperlaneMessage};
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct MessageApprovedEvent {
+    pub message_id: Bytes32,
+}
+
+#[blueprint]
+#[events(MessageApprovedEvent)]
+mod trusted_relayer_ism {
+
+    struct TrustedRelayerIsm {
+        // proof the relayer has to present, e.g. a badge or the signature of an account key
+        relayer: AccessRule,
+        // map from message id -> hash of the transaction the relayer approved the message in
+        approved_messages: KeyValueStore<Bytes32, Hash>,
+    }
+
+    impl TrustedRelayerIsm {
+        /// Proofs of the transaction are not visible to the ISM when the mailbox calls
+        /// "verify()", so the relayer rule is asserted in "approve()" instead. The relayer
+        /// approves the message and calls "Mailbox::process()" in the same transaction.
+        /// An approval is only valid in the transaction it was made in, so nobody else can
+        /// use or consume it and "verify()" can be called by any caller. This lets the ISM
+        /// be used as a submodule of other ISMs, e.g. the "AggregationIsm".
+        pub fn instantiate(relayer: AccessRule) -> Global<TrustedRelayerIsm> {
+            Self {
+                relayer,
+                approved_messages: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        pub fn module_type(&self) -> Types {
+            Types::Null
+        }
+
+        pub fn relayer(&self) -> AccessRule {
+            self.relayer.clone()
+        }
+
+        /// Returns whether the relayer approved the message in the current transaction
+        pub fn approved(&self, message_id: Bytes32) -> bool {
+            self.approved_messages
+                .get(&message_id)
+                .is_some_and(|transaction_hash| *transaction_hash == Runtime::transaction_hash())
+        }
+
+        /// Approves a message for delivery in the current transaction
+        pub fn approve(&mut self, raw_message: Vec<u8>) {
+            Runtime::assert_access_rule(self.relayer.clone());
+
+            let message: HyperlaneMessage = raw_message.into();
+            let message_id = message.id();
+            self.approved_messages
+                .insert(message_id, Runtime::transaction_hash());
+
+            Runtime::emit_event(MessageApprovedEvent { message_id });
+        }
+
+        /// Accepts a message only if the relayer approved it in the current transaction.
+        /// Metadata is ignored. The approval is consumed, so every approval verifies a
+        /// single delivery.
+        pub fn verify(&mut self, _metadata: Vec<u8>, raw_message: Vec<u8>) -> bool {
+            let message: HyperlaneMessage = raw_message.into();
+
+            self.approved_messages
+                .remove(&message.id())
+                .is_some_and(|transaction_hash| transaction_hash == Runtime::transaction_hash())
+        }
+    }
+}
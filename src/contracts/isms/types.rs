@@ -18,4 +18,10 @@ pub enum Types {
     Null,
     /// Ccip Read ISM (accepts offchain signature information)
     CcipRead,
+    /// Arbitrum L2 to L1 ISM (not supported, keeps the discriminators aligned with Hyperlane)
+    ArbL2ToL1,
+    /// Weighted Merkle Proof ISM (threshold of combined validator weight)
+    WeightedMerkleRootMultisig,
+    /// Weighted Message ID ISM (threshold of combined validator weight)
+    WeightedMessageIdMultisig,
 }
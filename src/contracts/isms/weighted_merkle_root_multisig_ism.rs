@@ -0,0 +1,71 @@
+use crate::contracts::isms::{
+    multisig_ism::{assert_valid_weighted_validator_set, verify_weighted_multisig},
+    types::Types,
+};
+use crate::types::merkle::merkle_root_from_branch;
+use crate::types::metadata::MultisigIsmMerkleRootMetadata;
+use crate::types::EthAddress;
+use crate::types::HyperlaneMessage;
+use scrypto::prelude::*;
+
+#[blueprint]
+mod weighted_merkle_root_multisig_ism {
+
+    struct WeightedMerkleRootMultisigIsm {
+        // validators sorted by address, each with its signing weight
+        validators: Vec<(EthAddress, u64)>,
+        threshold_weight: u64,
+    }
+
+    impl WeightedMerkleRootMultisigIsm {
+        pub fn instantiate(
+            validators: Vec<(EthAddress, u64)>,
+            threshold_weight: u64,
+        ) -> Global<WeightedMerkleRootMultisigIsm> {
+            assert_valid_weighted_validator_set(&validators, threshold_weight);
+
+            Self {
+                validators,
+                threshold_weight,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        pub fn module_type(&self) -> Types {
+            Types::WeightedMerkleRootMultisig
+        }
+
+        pub fn validators_and_threshold_weight(
+            &self,
+            _message: Vec<u8>,
+        ) -> (Vec<(EthAddress, u64)>, u64) {
+            (self.validators.clone(), self.threshold_weight)
+        }
+
+        pub fn verify(&mut self, metadata: Vec<u8>, message: Vec<u8>) -> bool {
+            let metadata: MultisigIsmMerkleRootMetadata = metadata.into();
+
+            let message: HyperlaneMessage = message.into();
+            let signed_root = merkle_root_from_branch(
+                message.id(),
+                &metadata.merkle_proof,
+                metadata.message_index,
+            );
+
+            let digest = message.digest(
+                metadata.origin_merkle_tree_hook,
+                signed_root.into(),
+                metadata.signed_checkpoint_index,
+            );
+
+            verify_weighted_multisig(
+                digest,
+                &metadata.validator_signatures,
+                &self.validators,
+                self.threshold_weight,
+            )
+        }
+    }
+}
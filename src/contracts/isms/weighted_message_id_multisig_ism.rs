@@ -0,0 +1,64 @@
+use crate::contracts::isms::multisig_ism::{
+    assert_valid_weighted_validator_set, verify_weighted_multisig,
+};
+use crate::contracts::isms::types::Types;
+use crate::types::metadata::MultisigIsmMessageIdMetadata;
+use crate::types::EthAddress;
+use crate::types::HyperlaneMessage;
+use scrypto::prelude::*;
+
+#[blueprint]
+mod weighted_message_id_multisig_ism {
+
+    struct WeightedMessageIdMultisigIsm {
+        // validators sorted by address, each with its signing weight
+        validators: Vec<(EthAddress, u64)>,
+        threshold_weight: u64,
+    }
+
+    impl WeightedMessageIdMultisigIsm {
+        pub fn instantiate(
+            validators: Vec<(EthAddress, u64)>,
+            threshold_weight: u64,
+        ) -> Global<WeightedMessageIdMultisigIsm> {
+            assert_valid_weighted_validator_set(&validators, threshold_weight);
+
+            Self {
+                validators,
+                threshold_weight,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        pub fn module_type(&self) -> Types {
+            Types::WeightedMessageIdMultisig
+        }
+
+        pub fn validators_and_threshold_weight(
+            &self,
+            _message: Vec<u8>,
+        ) -> (Vec<(EthAddress, u64)>, u64) {
+            (self.validators.clone(), self.threshold_weight)
+        }
+
+        pub fn verify(&mut self, metadata: Vec<u8>, message: Vec<u8>) -> bool {
+            let metadata: MultisigIsmMessageIdMetadata = metadata.into();
+
+            let message: HyperlaneMessage = message.into();
+            let digest = message.digest(
+                metadata.origin_merkle_tree_hook,
+                metadata.merkle_root,
+                metadata.merkle_index,
+            );
+
+            verify_weighted_multisig(
+                digest,
+                &metadata.validator_signatures,
+                &self.validators,
+                self.threshold_weight,
+            )
+        }
+    }
+}
@@ -6,7 +6,13 @@ use scrypto::blueprint;
 use scrypto::prelude::*;
 
 #[blueprint]
-#[events(DispatchEvent, DispatchIdEvent, ProcessIdEvent, ProcessEvent)]
+#[events(
+    DispatchEvent,
+    DispatchIdEvent,
+    ProcessIdEvent,
+    ProcessEvent,
+    MessageVerifiedEvent
+)]
 mod mailbox {
 
     enable_method_auth! {
@@ -15,6 +21,8 @@ mod mailbox {
             // Public Lookup
             local_domain => PUBLIC;
             delivered => PUBLIC;
+            delivery => PUBLIC;
+            message_status => PUBLIC;
             nonce => PUBLIC;
             processed => PUBLIC;
 
@@ -31,9 +39,23 @@ mod mailbox {
             set_required_hook => restrict_to: [OWNER];
 
             latest_dispatched_id => PUBLIC;
+            dispatched_message => PUBLIC;
+            dispatched_id => PUBLIC;
+
+            // Dispatch Storage
+            dispatch_storage => PUBLIC;
+            set_dispatch_storage => restrict_to: [OWNER];
+
             dispatch => PUBLIC;
             quote_dispatch => PUBLIC;
             process => PUBLIC;
+            process_batch => PUBLIC;
+            process_requirements => PUBLIC;
+            execute => PUBLIC;
+
+            // Two-Phase Delivery
+            two_phase_delivery => PUBLIC;
+            set_two_phase_delivery => restrict_to: [OWNER];
             recipient_ism => PUBLIC;
         }
     }
@@ -46,13 +68,25 @@ mod mailbox {
         default_hook: Option<ComponentAddress>,
         required_hook: Option<ComponentAddress>,
 
-        processed_messages: KeyValueStore<Bytes32, ()>,
+        processed_messages: KeyValueStore<Bytes32, Delivery>,
 
         // latest dispatched message, used for auth in hooks
         latest_dispatched_message: Bytes32,
 
         // sequence for process, used for better indexing
         process_sequence: u32,
+
+        // what is persisted of dispatched messages, nothing by default
+        dispatch_storage: DispatchStorage,
+        // map from nonce -> dispatched message, if messages are stored
+        dispatched_messages: KeyValueStore<u32, HyperlaneMessage>,
+        // map from nonce -> dispatched message id, if only ids are stored
+        dispatched_ids: KeyValueStore<u32, Bytes32>,
+
+        // if enabled, messages are verified in "process()" and delivered in "execute()"
+        two_phase_delivery: bool,
+        // map from message id -> raw message, for verified messages that are not executed yet
+        verified_messages: KeyValueStore<Bytes32, Vec<u8>>,
     }
 
     impl Mailbox {
@@ -78,11 +112,29 @@ mod mailbox {
                 processed_messages: KeyValueStore::new(),
                 latest_dispatched_message: Bytes32::zero(),
                 process_sequence: 0,
+                dispatch_storage: DispatchStorage::None,
+                dispatched_messages: KeyValueStore::new(),
+                dispatched_ids: KeyValueStore::new(),
+                two_phase_delivery: false,
+                verified_messages: KeyValueStore::new(),
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Fixed(rule!(require(
                 owner_badge.resource_address()
             ))))
+            // the mailbox publishes its default ISM and hook as metadata, so it has to be able
+            // to set its own metadata. Locking is denied, a locked entry couldn't be updated.
+            .metadata(metadata! {
+                roles {
+                    metadata_setter => rule!(
+                        require(owner_badge.resource_address())
+                            || require(NonFungibleGlobalId::global_caller_badge(component_address))
+                    );
+                    metadata_setter_updater => rule!(deny_all);
+                    metadata_locker => rule!(deny_all);
+                    metadata_locker_updater => rule!(deny_all);
+                }
+            })
             .with_address(address_reservation)
             .globalize();
 
@@ -107,20 +159,55 @@ mod mailbox {
             self.processed_messages.get(&message_id).is_some()
         }
 
+        pub fn message_status(&self, message_id: Bytes32) -> MessageStatus {
+            if self.delivered(message_id) {
+                MessageStatus::Executed
+            } else if self.verified_messages.get(&message_id).is_some() {
+                MessageStatus::Verified
+            } else {
+                MessageStatus::Unknown
+            }
+        }
+
+        pub fn two_phase_delivery(&self) -> bool {
+            self.two_phase_delivery
+        }
+
+        /// If enabled, "process()" only verifies messages and "execute()" delivers them to
+        /// the recipient. A failing handle aborts the whole transaction, so this is the only
+        /// way to keep a successful verification when the recipient can't handle the message.
+        pub fn set_two_phase_delivery(&mut self, enabled: bool) {
+            self.two_phase_delivery = enabled;
+        }
+
+        pub fn delivery(&self, message_id: Bytes32) -> Option<Delivery> {
+            self.processed_messages.get(&message_id).map(|x| x.clone())
+        }
+
         pub fn default_ism(&self) -> Option<ComponentAddress> {
             self.default_ism
         }
 
+        /// The default ISM is also published as metadata. Unlike the state of the mailbox,
+        /// the metadata can be read by ISMs while the mailbox processes a message.
         pub fn set_default_ism(&mut self, address: ComponentAddress) {
             self.default_ism = Some(address);
+
+            Runtime::global_component()
+                .set_metadata(DEFAULT_ISM_METADATA, GlobalAddress::from(address));
         }
 
         pub fn default_hook(&self) -> Option<ComponentAddress> {
             self.default_hook
         }
 
+        /// The default hook is also published as metadata. Unlike the state of the mailbox,
+        /// the metadata can be read by hooks while the mailbox dispatches a message.
         pub fn set_default_hook(&mut self, address: ComponentAddress) {
             self.default_hook = Some(address);
+
+            Runtime::global_component()
+                .set_metadata(DEFAULT_HOOK_METADATA, GlobalAddress::from(address));
         }
 
         pub fn required_hook(&self) -> Option<ComponentAddress> {
@@ -135,6 +222,27 @@ mod mailbox {
             self.latest_dispatched_message
         }
 
+        pub fn dispatch_storage(&self) -> DispatchStorage {
+            self.dispatch_storage
+        }
+
+        /// Only messages dispatched after the change are affected, already stored messages
+        /// and ids are kept.
+        pub fn set_dispatch_storage(&mut self, dispatch_storage: DispatchStorage) {
+            self.dispatch_storage = dispatch_storage;
+        }
+
+        pub fn dispatched_message(&self, nonce: u32) -> Option<HyperlaneMessage> {
+            self.dispatched_messages.get(&nonce).map(|x| x.clone())
+        }
+
+        pub fn dispatched_id(&self, nonce: u32) -> Option<Bytes32> {
+            self.dispatched_ids
+                .get(&nonce)
+                .map(|id| *id)
+                .or_else(|| self.dispatched_messages.get(&nonce).map(|x| x.id()))
+        }
+
         pub fn dispatch(
             &mut self,
             destination_domain: u32,
@@ -161,6 +269,18 @@ mod mailbox {
             self.latest_dispatched_message = message_id;
             self.nonce += 1;
 
+            match self.dispatch_storage {
+                DispatchStorage::None => {}
+                DispatchStorage::Ids => {
+                    self.dispatched_ids
+                        .insert(hyperlane_message.nonce, message_id);
+                }
+                DispatchStorage::Messages => {
+                    self.dispatched_messages
+                        .insert(hyperlane_message.nonce, hyperlane_message.clone());
+                }
+            }
+
             let mut payment = payment;
 
             if let Some(required_hook) = self.required_hook {
@@ -287,6 +407,40 @@ mod mailbox {
             raw_message: Vec<u8>,
             visible_components: Vec<ComponentAddress>,
         ) {
+            self.process_message(metadata, raw_message, visible_components);
+        }
+
+        /// Processes multiple messages in a single transaction, each entry consists of
+        /// (metadata, raw message, visible components).
+        /// If "skip_delivered" is set, messages that have already been delivered are skipped
+        /// instead of aborting the whole batch. Returns the ids of the processed messages.
+        pub fn process_batch(
+            &mut self,
+            messages: Vec<(Vec<u8>, Vec<u8>, Vec<ComponentAddress>)>,
+            skip_delivered: bool,
+        ) -> Vec<Bytes32> {
+            let mut processed = Vec::with_capacity(messages.len());
+
+            for (metadata, raw_message, visible_components) in messages {
+                if skip_delivered {
+                    let message: HyperlaneMessage = raw_message.clone().into();
+                    if self.message_status(message.id()) != MessageStatus::Unknown {
+                        continue;
+                    }
+                }
+
+                processed.push(self.process_message(metadata, raw_message, visible_components));
+            }
+
+            processed
+        }
+
+        fn process_message(
+            &mut self,
+            metadata: Vec<u8>,
+            raw_message: Vec<u8>,
+            visible_components: Vec<ComponentAddress>,
+        ) -> Bytes32 {
             let message: HyperlaneMessage = raw_message.clone().into();
 
             if self.local_domain != message.destination {
@@ -298,10 +452,9 @@ mod mailbox {
             }
 
             let message_id = message.id();
-            if self.delivered(message_id) {
+            if self.message_status(message_id) != MessageStatus::Unknown {
                 panic_error!("message already processed");
             }
-            self.processed_messages.insert(message_id, ());
 
             let recipient_component: ComponentAddress = message.recipient.into();
 
@@ -323,6 +476,49 @@ mod mailbox {
                 panic_error!("ISM verification failed");
             }
 
+            if self.two_phase_delivery {
+                self.verified_messages.insert(message_id, raw_message);
+                Runtime::emit_event(MessageVerifiedEvent { message_id });
+                return message_id;
+            }
+
+            self.deliver(message_id, message, raw_message, visible_components);
+
+            message_id
+        }
+
+        /// Delivers a message that has been verified in two-phase delivery mode. Anyone can call
+        /// this. If the recipient fails to handle the message, the message stays verified and
+        /// the execution can be retried, e.g. with other visible components.
+        pub fn execute(&mut self, message_id: Bytes32, visible_components: Vec<ComponentAddress>) {
+            let raw_message = self
+                .verified_messages
+                .remove(&message_id)
+                .expect(&format_error!(
+                    "message is not verified or already executed"
+                ));
+
+            let message: HyperlaneMessage = raw_message.clone().into();
+            self.deliver(message_id, message, raw_message, visible_components);
+        }
+
+        fn deliver(
+            &mut self,
+            message_id: Bytes32,
+            message: HyperlaneMessage,
+            raw_message: Vec<u8>,
+            visible_components: Vec<ComponentAddress>,
+        ) {
+            self.processed_messages.insert(
+                message_id,
+                Delivery {
+                    sequence: self.process_sequence,
+                    epoch: Runtime::current_epoch().number(),
+                    timestamp: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                    transaction_hash: Runtime::transaction_hash(),
+                },
+            );
+
             Runtime::emit_event(ProcessEvent {
                 origin: message.origin,
                 sender: message.sender,
@@ -336,6 +532,7 @@ mod mailbox {
 
             self.process_sequence += 1;
 
+            let recipient_component: ComponentAddress = message.recipient.into();
             ScryptoVmV1Api::object_call(
                 recipient_component.as_node_id(),
                 "handle",
@@ -343,6 +540,25 @@ mod mailbox {
             );
         }
 
+        /// Returns the components the recipient needs to handle the message, which have to be
+        /// passed as visible components to "process()". Relayers can preview this call instead
+        /// of parsing the error of a failed "process()".
+        /// The recipient has to implement "required_components(raw_message)".
+        pub fn process_requirements(&self, raw_message: Vec<u8>) -> Vec<ComponentAddress> {
+            let message: HyperlaneMessage = raw_message.clone().into();
+            let recipient_component: ComponentAddress = message.recipient.into();
+
+            let result = ScryptoVmV1Api::object_call(
+                recipient_component.as_node_id(),
+                "required_components",
+                scrypto_args!(raw_message),
+            );
+
+            scrypto_decode(&result).expect(&format_error!(
+                "failed to decode required components of recipient"
+            ))
+        }
+
         /// Returns the ISM (Interchain Security Module) for the given recipient address.
         pub fn recipient_ism(&self, recipient: ComponentAddress) -> Option<ComponentAddress> {
             // uses low-level calls to fetch the ISM for the given recipient address
@@ -357,6 +573,42 @@ mod mailbox {
     }
 }
 
+/// Metadata key of the default ISM of a mailbox
+pub const DEFAULT_ISM_METADATA: &str = "default_ism";
+
+/// Metadata key of the default hook of a mailbox
+pub const DEFAULT_HOOK_METADATA: &str = "default_hook";
+
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DispatchStorage {
+    /// Dispatched messages are only emitted as events
+    None,
+    /// The id of every dispatched message is stored by its nonce
+    Ids,
+    /// Every dispatched message is stored by its nonce
+    Messages,
+}
+
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MessageStatus {
+    /// The message has not been processed
+    Unknown,
+    /// The message has been verified, but not delivered to the recipient
+    Verified,
+    /// The message has been delivered to the recipient
+    Executed,
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct Delivery {
+    // process sequence of the message, equal to the sequence of the process events
+    pub sequence: u32,
+    pub epoch: u64,
+    // unix timestamp in seconds
+    pub timestamp: i64,
+    pub transaction_hash: Hash,
+}
+
 #[derive(ScryptoSbor, ScryptoEvent)]
 pub struct DispatchEvent {
     pub destination: u32,
@@ -384,3 +636,8 @@ pub struct ProcessEvent {
     pub recipient: Bytes32,
     pub sequence: u32,
 }
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct MessageVerifiedEvent {
+    pub message_id: Bytes32,
+}
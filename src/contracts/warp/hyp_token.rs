@@ -56,6 +56,7 @@ mod hyp_token {
             transfer_remote => PUBLIC;
             ism => PUBLIC;
             quote_remote_transfer => PUBLIC;
+            required_components => PUBLIC;
             // Mailbox Only
             handle => restrict_to: [mailbox_component];
             // Owner Only
@@ -333,6 +334,17 @@ mod hyp_token {
             scrypto_decode(&result).expect(&format_error!("failed to decode dispatch result"))
         }
 
+        /// Returns the components "handle()" needs to interact with, which the relayer has to
+        /// pass as visible components to "Mailbox::process()".
+        pub fn required_components(&self, raw_message: Vec<u8>) -> Vec<ComponentAddress> {
+            let hyperlane_message: HyperlaneMessage = raw_message.into();
+
+            let warp_payload = WarpPayload::try_from(hyperlane_message.body)
+                .expect("failed to parse warp payload");
+
+            vec![warp_payload.component_address()]
+        }
+
         /*
             This method is called by the mailbox when a message is sent to this component.
             Due to resource management in radix, the caller must provide a list
@@ -63,8 +63,17 @@ pub fn announcement_digest(
     eth_hash(keccak256_hash(domain_hash).as_ref())
 }
 
-/// recover the eth address from the signature of the given hash
-pub fn recover_eth_address(digest: &Hash, signature: &Secp256k1Signature) -> EthAddress {
+/// Digest an offchain gateway signs to attest a message for the given CCIP-read ISM
+pub fn ccip_read_digest(ism_address: Bytes32, message_id: Bytes32) -> Hash {
+    let mut bytes = "HYPERLANE_OFFCHAIN_LOOKUP".as_bytes().to_vec();
+    bytes.extend(ism_address.as_ref());
+    bytes.extend(message_id.as_ref());
+
+    eth_hash(keccak256_hash(bytes).as_ref())
+}
+
+/// Converts an ethereum signature (r || s || v) into the format of the CryptoUtils (v || r || s)
+pub fn to_recoverable_signature(signature: &Secp256k1Signature) -> Secp256k1Signature {
     // For the CryptoUtils the recovery Id must be moved to the beginning
     // And it must be converted from an eth id (27/28) to a normal id (0/1)
     let mut signature: Vec<u8> = signature.to_vec();
@@ -72,18 +81,40 @@ pub fn recover_eth_address(digest: &Hash, signature: &Secp256k1Signature) -> Eth
     // Sub 27 of the recovery id according to this - https://eips.ethereum.org/EIPS/eip-155
     signature.insert(0, last - 27);
 
-    let signature = Secp256k1Signature(
+    Secp256k1Signature(
         signature
             .try_into()
             .expect("sig: can't parse Secp256k1 signature"),
+    )
+}
+
+/// recover the eth address from the signature of the given hash
+pub fn recover_eth_address(digest: &Hash, signature: &Secp256k1Signature) -> EthAddress {
+    let pubkey = CryptoUtils::secp256k1_ecdsa_verify_and_key_recover_uncompressed(
+        digest,
+        to_recoverable_signature(signature),
     );
 
-    let pubkey =
-        CryptoUtils::secp256k1_ecdsa_verify_and_key_recover_uncompressed(digest, signature);
+    eth_address_from_public_key(&pubkey)
+}
 
-    // ethereum address is the hash of the uncompressed public key
+/// recover the compressed public key from the signature of the given hash.
+/// Unlike "recover_eth_address()" the key doesn't have to be hashed.
+pub fn recover_public_key(digest: &Hash, signature: &Secp256k1Signature) -> Secp256k1PublicKey {
+    CryptoUtils::secp256k1_ecdsa_verify_and_key_recover(digest, to_recoverable_signature(signature))
+}
+
+/// ethereum address is the hash of the uncompressed public key
+pub fn eth_address_from_public_key(public_key: &Secp256k1UncompressedPublicKey) -> EthAddress {
     // exclude the first byte - which is always 0x4 to indicate Secp256k1
-    let pubkey_bytes = pubkey.0;
-    let address = keccak256_hash(&pubkey_bytes[1..]);
+    let address = keccak256_hash(&public_key.0[1..]);
     address.into()
 }
+
+/// A compressed key consists of the x coordinate, prefixed with the parity of the y coordinate
+pub fn compress_public_key(public_key: &Secp256k1UncompressedPublicKey) -> Secp256k1PublicKey {
+    let mut compressed = [0u8; Secp256k1PublicKey::LENGTH];
+    compressed[0] = 0x02 | (public_key.0[64] & 1);
+    compressed[1..].copy_from_slice(&public_key.0[1..33]);
+    Secp256k1PublicKey(compressed)
+}
@@ -0,0 +1,61 @@
+use scrypto::prelude::*;
+
+const RANGE_SIZE: usize = 4;
+
+/// Format of metadata:
+/// [????:????] Metadata start/end uint32 ranges, packed as uint64, one per module
+/// [????:????] Metadata for each module, referenced by the ranges above
+///
+/// A module without metadata has a start offset of 0 and is skipped during
+/// verification.
+pub struct AggregationIsmMetadata {
+    bytes: Vec<u8>,
+}
+
+impl AggregationIsmMetadata {
+    /// Returns the metadata for the module at the given index, or `None` if the
+    /// relayer did not provide metadata for it.
+    pub fn metadata_at(&self, index: usize) -> Option<Vec<u8>> {
+        let (start, end) = self.metadata_range(index);
+        if start == 0 {
+            return None;
+        }
+
+        if start > end || end > self.bytes.len() {
+            panic!(
+                "AggregationMetadata: invalid metadata range for module {}",
+                index
+            );
+        }
+
+        Some(self.bytes[start..end].to_vec())
+    }
+
+    fn metadata_range(&self, index: usize) -> (usize, usize) {
+        let start = index * RANGE_SIZE * 2;
+        let mid = start + RANGE_SIZE;
+        let end = mid + RANGE_SIZE;
+
+        if end > self.bytes.len() {
+            panic!("AggregationMetadata: invalid metadata length");
+        }
+
+        let range_start: [u8; 4] = self.bytes[start..mid]
+            .try_into()
+            .expect("AggregationMetadata: invalid metadata length");
+        let range_end: [u8; 4] = self.bytes[mid..end]
+            .try_into()
+            .expect("AggregationMetadata: invalid metadata length");
+
+        (
+            u32::from_be_bytes(range_start) as usize,
+            u32::from_be_bytes(range_end) as usize,
+        )
+    }
+}
+
+impl From<Vec<u8>> for AggregationIsmMetadata {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+}
@@ -0,0 +1,24 @@
+use scrypto::crypto::Secp256k1Signature;
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct CcipReadIsmMetadata {
+    pub signature: Secp256k1Signature,
+}
+
+const SIGNATURE_LENGTH: usize = 65;
+
+/// Format of metadata:
+/// [   0:  65] Signature of the offchain gateway response
+impl From<Vec<u8>> for CcipReadIsmMetadata {
+    fn from(bytes: Vec<u8>) -> Self {
+        if bytes.len() != SIGNATURE_LENGTH {
+            panic!("CcipReadMetadata: invalid metadata length");
+        }
+
+        let signature = Secp256k1Signature::try_from(bytes.as_slice())
+            .expect("CcipReadMetadata: was unable to parse signature");
+
+        Self { signature }
+    }
+}
@@ -1,7 +1,11 @@
+mod aggregation;
+mod ccip_read;
 mod merkle_root;
 mod message_id;
 mod standard_hook;
 
+pub use aggregation::*;
+pub use ccip_read::*;
 pub use merkle_root::*;
 pub use message_id::*;
 pub use standard_hook::*;
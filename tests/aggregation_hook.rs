@@ -0,0 +1,227 @@
+use crate::common::Suite;
+use hyperlane_radix::types::Bytes32;
+use scrypto_test::prelude::*;
+
+mod common;
+
+fn create_igp(suite: &mut Suite) -> ComponentAddress {
+    let result = suite.instantiate_blueprint("InterchainGasPaymaster", manifest_args!(XRD));
+    result.0.expect_commit_success();
+    let (igp, owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    let configs = vec![(1337u32, ((10_000_000_000u128, 1u128), 10u128))];
+    suite
+        .call_method_with_badge(
+            igp,
+            "set_destination_gas_configs",
+            owner_badge,
+            manifest_args!(configs),
+        )
+        .expect_commit_success();
+
+    igp
+}
+
+fn setup_mailbox(suite: &mut Suite) -> (ComponentAddress, ResourceAddress) {
+    let result = suite.instantiate_blueprint("Mailbox", manifest_args!(100u32));
+    result.0.expect_commit_success();
+    (result.1.unwrap(), result.2.unwrap())
+}
+
+fn create_aggregation_hook(
+    suite: &mut Suite,
+    hooks: Vec<ComponentAddress>,
+) -> (TransactionReceipt, Option<ComponentAddress>) {
+    let result = suite.instantiate_blueprint("AggregationHook", manifest_args!(hooks));
+    (result.0, result.1)
+}
+
+/// Creates a mailbox with an aggregation hook of the given hooks as default hook
+fn setup_aggregation_mailbox(
+    suite: &mut Suite,
+    hooks: Vec<ComponentAddress>,
+) -> (ComponentAddress, ComponentAddress) {
+    let (mailbox, mailbox_owner_badge) = setup_mailbox(suite);
+    let (receipt, hook) = create_aggregation_hook(suite, hooks);
+    receipt.expect_commit_success();
+    let hook = hook.unwrap();
+
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_default_hook",
+            mailbox_owner_badge,
+            manifest_args!(hook),
+        )
+        .expect_commit_success();
+
+    (mailbox, hook)
+}
+
+fn dispatch(suite: &mut Suite, mailbox: ComponentAddress, payment: Decimal) -> TransactionReceipt {
+    let metadata = Some((Decimal::one(), Option::<Vec<u8>>::None));
+    let claimed_account_address = suite.account.address;
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(suite.account.address, XRD, payment)
+        .take_from_worktop(XRD, payment, "payment")
+        .call_method_with_name_lookup(mailbox, "dispatch", |lookup| {
+            manifest_args!(
+                1337u32,
+                Bytes32::zero(),
+                Vec::<u8>::new(),
+                None::<ComponentAddress>,
+                metadata,
+                vec![lookup.bucket("payment")],
+                ManifestValue::enum_variant(
+                    1u8,
+                    vec![ManifestValue::Custom {
+                        value: ManifestCustomValue::Address(ManifestAddress::Static(
+                            *claimed_account_address.as_node_id()
+                        )),
+                    }]
+                )
+            )
+        })
+        .deposit_batch(suite.account.address, ManifestExpression::EntireWorktop)
+        .build();
+
+    suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    )
+}
+
+fn post_dispatch(
+    suite: &mut Suite,
+    hook: ComponentAddress,
+    payment: Decimal,
+) -> TransactionReceipt {
+    let metadata = Some((Decimal::one(), Option::<Vec<u8>>::None));
+    let message = (
+        3u8,
+        0u32,
+        0u32,
+        Bytes32::zero(),
+        1337u32,
+        Bytes32::zero(),
+        Vec::<u8>::new(),
+    );
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(suite.account.address, XRD, payment)
+        .take_from_worktop(XRD, payment, "payment")
+        .call_method_with_name_lookup(hook, "post_dispatch", |lookup| {
+            manifest_args!(metadata, message, vec![lookup.bucket("payment")])
+        })
+        .build();
+
+    suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    )
+}
+
+#[test]
+fn test_create_empty_aggregation_hook() {
+    let mut suite = common::setup();
+    let (receipt, _) = create_aggregation_hook(&mut suite, vec![]);
+
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("AggregationHook: at least one hook is required"));
+}
+
+#[test]
+fn test_quote_dispatch_merges_child_quotes() {
+    let mut suite = common::setup();
+    let igp_1 = create_igp(&mut suite);
+    let igp_2 = create_igp(&mut suite);
+    let (_, hook) = setup_aggregation_mailbox(&mut suite, vec![igp_1, igp_2]);
+
+    let hooks: Vec<ComponentAddress> = suite.call_method_success(hook, "hooks", manifest_args!());
+    assert_eq!(hooks, vec![igp_1, igp_2]);
+
+    let metadata = Some((Decimal::one(), Option::<Vec<u8>>::None));
+    let quote: IndexMap<ResourceAddress, Decimal> = suite.call_method_success(
+        hook,
+        "quote_dispatch",
+        manifest_args!(
+            metadata,
+            (
+                3u8,
+                0u32,
+                0u32,
+                Bytes32::zero(),
+                1337u32,
+                Bytes32::zero(),
+                Vec::<u8>::new()
+            )
+        ),
+    );
+
+    // Each IGP quotes 11 XRD (1 gas + 10 overhead)
+    let expected: indexmap::IndexMap<ResourceAddress, Decimal> =
+        IndexMap::from_iter(vec![(XRD, Decimal::from(22))]);
+    assert_eq!(quote, expected);
+}
+
+#[test]
+fn test_post_dispatch_pays_all_hooks() {
+    let mut suite = common::setup();
+    let igp_1 = create_igp(&mut suite);
+    let igp_2 = create_igp(&mut suite);
+    let (mailbox, _) = setup_aggregation_mailbox(&mut suite, vec![igp_1, igp_2]);
+
+    // this should consume the entire bucket
+    dispatch(&mut suite, mailbox, dec!(22)).expect_commit_success();
+
+    assert_eq!(suite.ledger.get_component_balance(igp_1, XRD), dec!(11));
+    assert_eq!(suite.ledger.get_component_balance(igp_2, XRD), dec!(11));
+}
+
+#[test]
+fn test_post_dispatch_insufficient_payment() {
+    let mut suite = common::setup();
+    let igp_1 = create_igp(&mut suite);
+    let igp_2 = create_igp(&mut suite);
+    let (mailbox, _) = setup_aggregation_mailbox(&mut suite, vec![igp_1, igp_2]);
+
+    // the first hook consumes 11 XRD, leaving too little for the second one
+    let receipt = dispatch(&mut suite, mailbox, dec!(12));
+
+    assert!(format!("{:?}", receipt.expect_failure())
+        .contains("InterchainGasPaymaster: payment for gas does not match IGP quote. quote: 11"));
+}
+
+#[test]
+fn test_post_dispatch_without_mailbox() {
+    let mut suite = common::setup();
+    let igp = create_igp(&mut suite);
+    let (receipt, hook) = create_aggregation_hook(&mut suite, vec![igp]);
+    receipt.expect_commit_success();
+
+    // the aggregation hook only forwards, the child hooks check their callers
+    post_dispatch(&mut suite, hook.unwrap(), dec!(11)).expect_commit_success();
+    assert_eq!(suite.ledger.get_component_balance(igp, XRD), dec!(11));
+}
+
+#[test]
+fn test_nested_aggregation_hooks() {
+    let mut suite = common::setup();
+    let igp_1 = create_igp(&mut suite);
+    let igp_2 = create_igp(&mut suite);
+    let (receipt, inner) = create_aggregation_hook(&mut suite, vec![igp_2]);
+    receipt.expect_commit_success();
+    let (mailbox, _) = setup_aggregation_mailbox(&mut suite, vec![igp_1, inner.unwrap()]);
+
+    dispatch(&mut suite, mailbox, dec!(22)).expect_commit_success();
+
+    assert_eq!(suite.ledger.get_component_balance(igp_1, XRD), dec!(11));
+    assert_eq!(suite.ledger.get_component_balance(igp_2, XRD), dec!(11));
+}
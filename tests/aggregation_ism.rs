@@ -0,0 +1,193 @@
+use crate::common::Suite;
+use hyperlane_radix::types::EthAddress;
+use hyperlane_radix::types::HyperlaneMessage;
+use scrypto_test::prelude::*;
+
+mod common;
+
+fn create_noop_ism(suite: &mut Suite) -> ComponentAddress {
+    let result = suite.instantiate_blueprint("NoopIsm", manifest_args!());
+    result.0.expect_commit_success();
+    result.1.unwrap()
+}
+
+fn create_message_id_multisig_ism(
+    suite: &mut Suite,
+    validators: Vec<EthAddress>,
+    threshold: usize,
+) -> ComponentAddress {
+    let result = suite.instantiate_blueprint(
+        "MessageIdMultisigIsm",
+        manifest_args!(validators, threshold),
+    );
+    result.0.expect_commit_success();
+    result.1.unwrap()
+}
+
+fn create_aggregation_ism(
+    suite: &mut Suite,
+    modules: Vec<ComponentAddress>,
+    threshold: usize,
+) -> (TransactionReceipt, Option<ComponentAddress>) {
+    let result = suite.instantiate_blueprint("AggregationIsm", manifest_args!(modules, threshold));
+    (result.0, result.1)
+}
+
+/// Encodes metadata following the EVM aggregation layout: one (start, end) range per module,
+/// followed by the concatenated module metadata.
+fn aggregation_metadata(modules: Vec<Option<Vec<u8>>>) -> Vec<u8> {
+    let mut ranges = Vec::new();
+    let mut body = Vec::new();
+    let header_len = modules.len() * 8;
+
+    for metadata in modules {
+        match metadata {
+            Some(metadata) => {
+                let start = (header_len + body.len()) as u32;
+                let end = start + metadata.len() as u32;
+                ranges.extend_from_slice(&start.to_be_bytes());
+                ranges.extend_from_slice(&end.to_be_bytes());
+                body.extend(metadata);
+            }
+            None => ranges.extend_from_slice(&[0u8; 8]),
+        }
+    }
+
+    ranges.extend(body);
+    ranges
+}
+
+fn dummy_message() -> Vec<u8> {
+    HyperlaneMessage {
+        version: 3,
+        nonce: 0,
+        origin: 1,
+        sender: Default::default(),
+        destination: 0,
+        recipient: Default::default(),
+        body: vec![],
+    }
+    .into()
+}
+
+fn verify(
+    suite: &mut Suite,
+    component_address: ComponentAddress,
+    metadata: Vec<u8>,
+    message: Vec<u8>,
+) -> TransactionReceipt {
+    suite.call_method(
+        component_address,
+        "verify",
+        manifest_args!(metadata, message),
+    )
+}
+
+#[test]
+fn test_create_aggregation_ism_invalid_threshold() {
+    let mut suite = common::setup();
+    let noop_ism = create_noop_ism(&mut suite);
+
+    let (receipt, _) = create_aggregation_ism(&mut suite, vec![noop_ism], 2);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("AggregationIsm: threshold must be less than or equal to the number of modules"));
+
+    let (receipt, _) = create_aggregation_ism(&mut suite, vec![noop_ism], 0);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("AggregationIsm: threshold must be greater than zero"));
+}
+
+#[test]
+fn test_verify_skips_modules_without_metadata() {
+    // Arrange
+    let mut suite = common::setup();
+    let noop_ism_1 = create_noop_ism(&mut suite);
+    let noop_ism_2 = create_noop_ism(&mut suite);
+    let (receipt, aggregation_ism) =
+        create_aggregation_ism(&mut suite, vec![noop_ism_1, noop_ism_2], 1);
+    receipt.expect_commit_success();
+
+    let (modules, threshold): (Vec<ComponentAddress>, usize) = suite.call_method_success(
+        aggregation_ism.unwrap(),
+        "modules_and_threshold",
+        manifest_args!(dummy_message()),
+    );
+    assert_eq!(modules, vec![noop_ism_1, noop_ism_2]);
+    assert_eq!(threshold, 1);
+
+    // Act
+    let metadata = aggregation_metadata(vec![None, Some(vec![])]);
+    let receipt = verify(
+        &mut suite,
+        aggregation_ism.unwrap(),
+        metadata,
+        dummy_message(),
+    );
+
+    // Assert
+    let call_result = receipt.expect_commit_success().outcome.expect_success();
+    call_result[1].expect_return_value(&true);
+}
+
+#[test]
+fn test_verify_threshold_not_reached() {
+    // Arrange
+    let mut suite = common::setup();
+    let noop_ism_1 = create_noop_ism(&mut suite);
+    let noop_ism_2 = create_noop_ism(&mut suite);
+    let (receipt, aggregation_ism) =
+        create_aggregation_ism(&mut suite, vec![noop_ism_1, noop_ism_2], 2);
+    receipt.expect_commit_success();
+
+    // Act
+    let metadata = aggregation_metadata(vec![Some(vec![]), None]);
+    let receipt = verify(
+        &mut suite,
+        aggregation_ism.unwrap(),
+        metadata,
+        dummy_message(),
+    );
+
+    // Assert
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("AggregationIsm: threshold not reached"));
+}
+
+#[test]
+fn test_verify_with_message_id_multisig_ism() {
+    // Arrange
+    let message = hex::decode("0300000000000000010000000000000000000000007ff2bf58c38a41ad7c9cbc14e780e8a7edbbd48d00002105000000000000000000000000811808dd29ba8b0fc6c0ec0b5537035e5974516248656c6c6f21").unwrap();
+    let multisig_metadata = hex::decode("00000000000000000000000048e6c30b97748d1e2e03bf3e9fbe3890ca5f8ccadb278688f4f929bb03c76e57866ca41290dc63a1069752507fe6d20f307f1538000000003aeb79d0e542b8363144fe5286b1f8f6392d75d3220d9eca0ac20bb0cd41236d0e5eafcce7e6105cc282caa68ce73d095f80f111cde5a8f13e80bd8ddb0b91271b").unwrap();
+    let validator: [u8; 20] = hex::decode("03c842db86a6a3e524d4a6615390c1ea8e2b9541")
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    let mut suite = common::setup();
+    let multisig_ism = create_message_id_multisig_ism(&mut suite, vec![validator.into()], 1);
+    let noop_ism = create_noop_ism(&mut suite);
+    let (receipt, aggregation_ism) =
+        create_aggregation_ism(&mut suite, vec![multisig_ism, noop_ism], 2);
+    receipt.expect_commit_success();
+
+    // Act
+    let metadata = aggregation_metadata(vec![Some(multisig_metadata.clone()), Some(vec![])]);
+    let receipt = verify(
+        &mut suite,
+        aggregation_ism.unwrap(),
+        metadata,
+        message.clone(),
+    );
+
+    // Assert
+    let call_result = receipt.expect_commit_success().outcome.expect_success();
+    call_result[1].expect_return_value(&true);
+
+    // A tampered checkpoint root for the multisig module must fail the whole aggregation
+    let mut invalid_metadata = multisig_metadata;
+    invalid_metadata[32] = 0xee;
+    let metadata = aggregation_metadata(vec![Some(invalid_metadata), Some(vec![])]);
+    let receipt = verify(&mut suite, aggregation_ism.unwrap(), metadata, message);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: threshold not reached"));
+}
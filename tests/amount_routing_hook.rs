@@ -0,0 +1,202 @@
+use crate::common::Suite;
+use hyperlane_radix::types::{warp_payload::WarpPayload, Bytes32};
+use scrypto_test::prelude::*;
+
+mod common;
+
+fn create_igp(suite: &mut Suite, gas_overhead: u128) -> ComponentAddress {
+    let result = suite.instantiate_blueprint("InterchainGasPaymaster", manifest_args!(XRD));
+    result.0.expect_commit_success();
+    let (igp, owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    let configs = vec![(1337u32, ((10_000_000_000u128, 1u128), gas_overhead))];
+    suite
+        .call_method_with_badge(
+            igp,
+            "set_destination_gas_configs",
+            owner_badge,
+            manifest_args!(configs),
+        )
+        .expect_commit_success();
+
+    igp
+}
+
+/// Creates a mailbox with an amount routing hook as default hook
+fn create_amount_routing_hook(
+    suite: &mut Suite,
+    lower: ComponentAddress,
+    upper: ComponentAddress,
+    threshold: Decimal,
+) -> (ComponentAddress, ComponentAddress) {
+    let result = suite.instantiate_blueprint("Mailbox", manifest_args!(100u32));
+    result.0.expect_commit_success();
+    let (mailbox, mailbox_owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    let result = suite.instantiate_blueprint(
+        "AmountRoutingHook",
+        manifest_args!(lower, upper, threshold, 18u32),
+    );
+    result.0.expect_commit_success();
+    let hook = result.1.unwrap();
+
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_default_hook",
+            mailbox_owner_badge,
+            manifest_args!(hook),
+        )
+        .expect_commit_success();
+
+    (mailbox, hook)
+}
+
+fn quote_dispatch(suite: &mut Suite, hook: ComponentAddress, body: Vec<u8>) -> TransactionReceipt {
+    let metadata = Some((Decimal::one(), Option::<Vec<u8>>::None));
+    suite.call_method(
+        hook,
+        "quote_dispatch",
+        manifest_args!(
+            metadata,
+            (
+                3u8,
+                0u32,
+                0u32,
+                Bytes32::zero(),
+                1337u32,
+                Bytes32::zero(),
+                body
+            )
+        ),
+    )
+}
+
+fn transfer_body(amount: Decimal) -> Vec<u8> {
+    WarpPayload::try_new_with_divisibility(Bytes32::zero(), amount, 18)
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn test_route_by_amount() {
+    let mut suite = common::setup();
+    let lower = create_igp(&mut suite, 10);
+    let upper = create_igp(&mut suite, 20);
+    let (_, hook) = create_amount_routing_hook(&mut suite, lower, upper, dec!(100));
+
+    let quotes = vec![
+        (dec!(99), dec!(11)),
+        // the threshold itself is routed to the upper hook
+        (dec!(100), dec!(21)),
+        (dec!(1000), dec!(21)),
+    ];
+
+    for (amount, quote) in quotes {
+        let receipt = quote_dispatch(&mut suite, hook, transfer_body(amount));
+        let call_result = receipt.expect_commit_success().outcome.expect_success();
+        let expected: indexmap::IndexMap<ResourceAddress, Decimal> =
+            IndexMap::from_iter(vec![(XRD, quote)]);
+        call_result[1].expect_return_value(&expected);
+    }
+}
+
+#[test]
+fn test_post_dispatch_routes_payment() {
+    let mut suite = common::setup();
+    let lower = create_igp(&mut suite, 10);
+    let upper = create_igp(&mut suite, 20);
+    let (mailbox, _) = create_amount_routing_hook(&mut suite, lower, upper, dec!(100));
+
+    let metadata = Some((Decimal::one(), Option::<Vec<u8>>::None));
+    let body = transfer_body(dec!(500));
+    let claimed_account_address = suite.account.address;
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(suite.account.address, XRD, dec!(21))
+        .take_from_worktop(XRD, dec!(21), "payment")
+        .call_method_with_name_lookup(mailbox, "dispatch", |lookup| {
+            manifest_args!(
+                1337u32,
+                Bytes32::zero(),
+                body,
+                None::<ComponentAddress>,
+                metadata,
+                vec![lookup.bucket("payment")],
+                ManifestValue::enum_variant(
+                    1u8,
+                    vec![ManifestValue::Custom {
+                        value: ManifestCustomValue::Address(ManifestAddress::Static(
+                            *claimed_account_address.as_node_id()
+                        )),
+                    }]
+                )
+            )
+        })
+        .deposit_batch(suite.account.address, ManifestExpression::EntireWorktop)
+        .build();
+
+    let receipt = suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    );
+
+    receipt.expect_commit_success();
+    assert_eq!(suite.ledger.get_component_balance(lower, XRD), dec!(0));
+    assert_eq!(suite.ledger.get_component_balance(upper, XRD), dec!(21));
+}
+
+#[test]
+fn test_post_dispatch_without_mailbox() {
+    let mut suite = common::setup();
+    let lower = create_igp(&mut suite, 10);
+    let upper = create_igp(&mut suite, 20);
+    let (_, hook) = create_amount_routing_hook(&mut suite, lower, upper, dec!(100));
+
+    let metadata = Some((Decimal::one(), Option::<Vec<u8>>::None));
+    let message = (
+        3u8,
+        0u32,
+        0u32,
+        Bytes32::zero(),
+        1337u32,
+        Bytes32::zero(),
+        transfer_body(dec!(500)),
+    );
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(suite.account.address, XRD, dec!(21))
+        .take_from_worktop(XRD, dec!(21), "payment")
+        .call_method_with_name_lookup(hook, "post_dispatch", |lookup| {
+            manifest_args!(metadata, message, vec![lookup.bucket("payment")])
+        })
+        .build();
+
+    let receipt = suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    );
+
+    // the routing hook only forwards, the child hooks check their callers
+    receipt.expect_commit_success();
+    assert_eq!(suite.ledger.get_component_balance(upper, XRD), dec!(21));
+}
+
+#[test]
+fn test_invalid_payload() {
+    let mut suite = common::setup();
+    let lower = create_igp(&mut suite, 10);
+    let upper = create_igp(&mut suite, 20);
+    let (_, hook) = create_amount_routing_hook(&mut suite, lower, upper, dec!(100));
+
+    let receipt = quote_dispatch(&mut suite, hook, vec![1u8, 2u8, 3u8]);
+
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("AmountRoutingHook: failed to decode warp payload"));
+}
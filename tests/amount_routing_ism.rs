@@ -0,0 +1,123 @@
+use crate::common::Suite;
+use hyperlane_radix::types::{warp_payload::WarpPayload, Bytes32, HyperlaneMessage};
+use scrypto_test::prelude::*;
+
+mod common;
+
+fn create_noop_ism(suite: &mut Suite) -> ComponentAddress {
+    let result = suite.instantiate_blueprint("NoopIsm", manifest_args!());
+    result.0.expect_commit_success();
+    result.1.unwrap()
+}
+
+fn create_paused_ism(suite: &mut Suite) -> ComponentAddress {
+    let pauser_badge =
+        suite
+            .ledger
+            .create_fungible_resource(dec!(1), DIVISIBILITY_NONE, suite.account.address);
+    let result = suite.instantiate_blueprint("PausableIsm", manifest_args!(pauser_badge));
+    result.0.expect_commit_success();
+    let ism = result.1.unwrap();
+
+    suite
+        .call_method_with_badge(ism, "pause", pauser_badge, manifest_args!())
+        .expect_commit_success();
+
+    ism
+}
+
+fn create_amount_routing_ism(
+    suite: &mut Suite,
+    lower: ComponentAddress,
+    upper: ComponentAddress,
+    threshold: Decimal,
+) -> ComponentAddress {
+    let result = suite.instantiate_blueprint(
+        "AmountRoutingIsm",
+        manifest_args!(lower, upper, threshold, 18u32),
+    );
+    result.0.expect_commit_success();
+    result.1.unwrap()
+}
+
+fn transfer_message(body: Vec<u8>) -> Vec<u8> {
+    HyperlaneMessage {
+        version: 3,
+        nonce: 0,
+        origin: 1337,
+        sender: Default::default(),
+        destination: 0,
+        recipient: Default::default(),
+        body,
+    }
+    .into()
+}
+
+fn transfer_body(amount: Decimal) -> Vec<u8> {
+    WarpPayload::try_new_with_divisibility(Bytes32::zero(), amount, 18)
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn test_route_by_amount() {
+    let mut suite = common::setup();
+    let lower = create_noop_ism(&mut suite);
+    let upper = create_paused_ism(&mut suite);
+    let ism = create_amount_routing_ism(&mut suite, lower, upper, dec!(100));
+
+    let routes = vec![
+        (dec!(99), lower),
+        // the threshold itself is routed to the upper ISM
+        (dec!(100), upper),
+        (dec!(1000), upper),
+    ];
+
+    for (amount, expected) in routes {
+        let route: ComponentAddress = suite.call_method_success(
+            ism,
+            "route",
+            manifest_args!(transfer_message(transfer_body(amount))),
+        );
+        assert_eq!(route, expected);
+    }
+}
+
+#[test]
+fn test_verify_delegates_by_amount() {
+    let mut suite = common::setup();
+    let lower = create_noop_ism(&mut suite);
+    let upper = create_paused_ism(&mut suite);
+    let ism = create_amount_routing_ism(&mut suite, lower, upper, dec!(100));
+
+    let verified: bool = suite.call_method_success(
+        ism,
+        "verify",
+        manifest_args!(Vec::<u8>::new(), transfer_message(transfer_body(dec!(10)))),
+    );
+    assert!(verified);
+
+    let verified: bool = suite.call_method_success(
+        ism,
+        "verify",
+        manifest_args!(Vec::<u8>::new(), transfer_message(transfer_body(dec!(500)))),
+    );
+    assert!(!verified);
+}
+
+#[test]
+fn test_invalid_payload() {
+    let mut suite = common::setup();
+    let lower = create_noop_ism(&mut suite);
+    let upper = create_noop_ism(&mut suite);
+    let ism = create_amount_routing_ism(&mut suite, lower, upper, dec!(100));
+
+    let receipt = suite.call_method(
+        ism,
+        "verify",
+        manifest_args!(Vec::<u8>::new(), transfer_message(vec![1u8, 2u8, 3u8])),
+    );
+
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("AmountRoutingIsm: failed to decode warp payload"));
+}
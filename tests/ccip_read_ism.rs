@@ -0,0 +1,116 @@
+use crate::common::Suite;
+use hyperlane_radix::types::{ccip_read_digest, Bytes32, EthAddress, HyperlaneMessage};
+use scrypto_test::prelude::*;
+
+mod common;
+
+fn create_ccip_read_ism(
+    suite: &mut Suite,
+    urls: Vec<String>,
+    signers: Vec<EthAddress>,
+) -> (ComponentAddress, ResourceAddress) {
+    let result = suite.instantiate_blueprint("CcipReadIsm", manifest_args!(urls, signers));
+    result.0.expect_commit_success();
+    (result.1.unwrap(), result.2.unwrap())
+}
+
+fn raw_message() -> Vec<u8> {
+    HyperlaneMessage {
+        version: 3,
+        nonce: 0,
+        origin: 1337,
+        sender: Default::default(),
+        destination: 0,
+        recipient: Default::default(),
+        body: vec![1, 2, 3],
+    }
+    .into()
+}
+
+fn eth_address(private_key: &Secp256k1PrivateKey) -> EthAddress {
+    let digest = hash("address");
+    let public_key =
+        verify_and_recover_secp256k1_uncompressed(&digest, &private_key.sign(&digest)).unwrap();
+    keccak256_hash(&public_key.0[1..]).into()
+}
+
+/// Signs the gateway response and converts the signature into the ethereum format
+fn sign_response(private_key: &Secp256k1PrivateKey, ism: ComponentAddress) -> Vec<u8> {
+    let message: HyperlaneMessage = raw_message().into();
+    let digest = ccip_read_digest(ism.into(), message.id());
+
+    let signature = private_key.sign(&digest).to_vec();
+    let mut eth_signature = signature[1..].to_vec();
+    eth_signature.push(signature[0] + 27);
+    eth_signature
+}
+
+#[test]
+fn test_offchain_verify_info() {
+    let mut suite = common::setup();
+    let urls = vec!["https://gateway.example.com/{data}".to_string()];
+    let (ism, _) = create_ccip_read_ism(&mut suite, urls.clone(), vec![]);
+
+    let (info_urls, call_data): (Vec<String>, Vec<u8>) = suite.call_method_success(
+        ism,
+        "get_offchain_verify_info",
+        manifest_args!(raw_message()),
+    );
+
+    let ism_address: Bytes32 = ism.into();
+    let mut expected_call_data = ism_address.as_ref().to_vec();
+    expected_call_data.extend(raw_message());
+
+    assert_eq!(info_urls, urls);
+    assert_eq!(call_data, expected_call_data);
+}
+
+#[test]
+fn test_verify_signed_response() {
+    let mut suite = common::setup();
+    let signer = Secp256k1PrivateKey::from_u64(1).unwrap();
+    let other = Secp256k1PrivateKey::from_u64(2).unwrap();
+    let (ism, owner_badge) = create_ccip_read_ism(&mut suite, vec![], vec![eth_address(&signer)]);
+
+    let verified: bool = suite.call_method_success(
+        ism,
+        "verify",
+        manifest_args!(sign_response(&signer, ism), raw_message()),
+    );
+    assert!(verified);
+
+    let verified: bool = suite.call_method_success(
+        ism,
+        "verify",
+        manifest_args!(sign_response(&other, ism), raw_message()),
+    );
+    assert!(!verified);
+
+    // Rotate the signer
+    suite
+        .call_method_with_badge(
+            ism,
+            "set_signers",
+            owner_badge,
+            manifest_args!(vec![eth_address(&other)]),
+        )
+        .expect_commit_success();
+
+    let verified: bool = suite.call_method_success(
+        ism,
+        "verify",
+        manifest_args!(sign_response(&other, ism), raw_message()),
+    );
+    assert!(verified);
+}
+
+#[test]
+fn test_invalid_metadata() {
+    let mut suite = common::setup();
+    let (ism, _) = create_ccip_read_ism(&mut suite, vec![], vec![]);
+
+    let receipt = suite.call_method(ism, "verify", manifest_args!(vec![0u8; 64], raw_message()));
+
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("CcipReadMetadata: invalid metadata length"));
+}
@@ -0,0 +1,250 @@
+use crate::common::{hex_str_to_bytes32, Suite};
+use hyperlane_radix::types::{Bytes32, HyperlaneMessage};
+use scrypto_test::prelude::*;
+
+mod common;
+
+// Transfer of 50 tokens from domain 1337 to a synthetic token on domain 1000
+const PAYLOAD: &str = "0300000000000005390000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496000003e80000c07341fadfb99d506736cf979374b560851b181d9e83e225d5437ac270e80000c1f7abd48c518b8ebdc6a35abfbe78583725a97eabdc99224571e0d11d42000000000000000000000000000000000000000000000002b5e3af16b1880000";
+
+fn create_mailbox(suite: &mut Suite) -> (ComponentAddress, ResourceAddress) {
+    let result = suite.instantiate_blueprint("Mailbox", manifest_args!(100u32));
+    result.0.expect_commit_success();
+    (result.1.unwrap(), result.2.unwrap())
+}
+
+fn create_noop_ism(suite: &mut Suite) -> ComponentAddress {
+    let result = suite.instantiate_blueprint("NoopIsm", manifest_args!());
+    result.0.expect_commit_success();
+    result.1.unwrap()
+}
+
+fn create_fallback_routing_ism(
+    suite: &mut Suite,
+    mailbox: ComponentAddress,
+    routes: Vec<(u32, ComponentAddress)>,
+) -> (ComponentAddress, ResourceAddress) {
+    let result =
+        suite.instantiate_blueprint("DefaultFallbackRoutingIsm", manifest_args!(mailbox, routes));
+    result.0.expect_commit_success();
+    (result.1.unwrap(), result.2.unwrap())
+}
+
+fn raw_message(origin: u32) -> Vec<u8> {
+    HyperlaneMessage {
+        version: 3,
+        nonce: 0,
+        origin,
+        sender: Default::default(),
+        destination: 0,
+        recipient: Default::default(),
+        body: vec![],
+    }
+    .into()
+}
+
+fn set_default_ism(
+    suite: &mut Suite,
+    mailbox: ComponentAddress,
+    owner_badge: ResourceAddress,
+    ism: ComponentAddress,
+) {
+    suite
+        .call_method_with_badge(mailbox, "set_default_ism", owner_badge, manifest_args!(ism))
+        .expect_commit_success();
+}
+
+#[test]
+fn test_route_falls_back_to_default_ism() {
+    let mut suite = common::setup();
+    let (mailbox, mailbox_owner_badge) = create_mailbox(&mut suite);
+    let default_ism = create_noop_ism(&mut suite);
+    let override_ism = create_noop_ism(&mut suite);
+    set_default_ism(&mut suite, mailbox, mailbox_owner_badge, default_ism);
+
+    let (ism, _) = create_fallback_routing_ism(&mut suite, mailbox, vec![(17, override_ism)]);
+
+    let route: ComponentAddress =
+        suite.call_method_success(ism, "route", manifest_args!(raw_message(17)));
+    assert_eq!(route, override_ism);
+
+    let route: ComponentAddress =
+        suite.call_method_success(ism, "route", manifest_args!(raw_message(18)));
+    assert_eq!(route, default_ism);
+
+    let verified: bool = suite.call_method_success(
+        ism,
+        "verify",
+        manifest_args!(Vec::<u8>::new(), raw_message(18)),
+    );
+    assert!(verified);
+}
+
+#[test]
+fn test_no_default_ism() {
+    let mut suite = common::setup();
+    let (mailbox, _) = create_mailbox(&mut suite);
+    let (ism, _) = create_fallback_routing_ism(&mut suite, mailbox, vec![]);
+
+    let receipt = suite.call_method(
+        ism,
+        "verify",
+        manifest_args!(Vec::<u8>::new(), raw_message(18)),
+    );
+
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("DefaultFallbackRoutingIsm: no ISM for route 18"));
+}
+
+#[test]
+fn test_fallback_to_itself() {
+    let mut suite = common::setup();
+    let (mailbox, mailbox_owner_badge) = create_mailbox(&mut suite);
+    let (ism, _) = create_fallback_routing_ism(&mut suite, mailbox, vec![]);
+
+    // The routing ISM can't fall back to itself
+    set_default_ism(&mut suite, mailbox, mailbox_owner_badge, ism);
+    let receipt = suite.call_method(ism, "route", manifest_args!(raw_message(18)));
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("DefaultFallbackRoutingIsm: fallback ISM can not be the routing ISM itself"));
+}
+
+#[test]
+fn test_fallback_follows_default_ism() {
+    let mut suite = common::setup();
+    let (mailbox, mailbox_owner_badge) = create_mailbox(&mut suite);
+    let old_ism = create_noop_ism(&mut suite);
+    let new_ism = create_noop_ism(&mut suite);
+    set_default_ism(&mut suite, mailbox, mailbox_owner_badge, old_ism);
+    let (ism, _) = create_fallback_routing_ism(&mut suite, mailbox, vec![]);
+
+    set_default_ism(&mut suite, mailbox, mailbox_owner_badge, new_ism);
+    let fallback: Option<ComponentAddress> =
+        suite.call_method_success(ism, "fallback_ism", manifest_args!());
+    assert_eq!(fallback, Some(new_ism));
+    let route: ComponentAddress =
+        suite.call_method_success(ism, "route", manifest_args!(raw_message(18)));
+    assert_eq!(route, new_ism);
+}
+
+/// Creates a synthetic token on domain 1000 that receives the transfer in `PAYLOAD` and
+/// is secured by the given ISM
+fn create_token(
+    suite: &mut Suite,
+    mailbox: ComponentAddress,
+    ism: ComponentAddress,
+) -> ComponentAddress {
+    let result = suite.instantiate_blueprint(
+        "HypToken",
+        manifest_args!(
+            ManifestValue::enum_variant(
+                1u8,
+                vec![
+                    ManifestValue::String {
+                        value: "Eth".to_string()
+                    },
+                    ManifestValue::String {
+                        value: "Ether".to_string()
+                    },
+                    ManifestValue::String {
+                        value: "Native ETH from Ethereum".to_string()
+                    },
+                    ManifestValue::U8 { value: 18 },
+                ]
+            ),
+            mailbox
+        ),
+    );
+    result.0.expect_commit_success();
+    let (token, token_owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    let remote_router: Bytes32 =
+        hex_str_to_bytes32("0000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496");
+    suite
+        .call_method_with_badge(
+            token,
+            "enroll_remote_router",
+            token_owner_badge,
+            manifest_args!(1337u32, remote_router, dec!(12)),
+        )
+        .expect_commit_success();
+    suite
+        .call_method_with_badge(
+            token,
+            "set_ism",
+            token_owner_badge,
+            manifest_args!(Some(ism)),
+        )
+        .expect_commit_success();
+
+    token
+}
+
+#[test]
+fn test_process_uses_current_default_ism() {
+    let mut suite = common::setup();
+    let result = suite.instantiate_blueprint("Mailbox", manifest_args!(1000u32));
+    result.0.expect_commit_success();
+    let (mailbox, mailbox_owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    // an ISM that rejects every message without a relayer approval
+    let result = suite.instantiate_blueprint("TrustedRelayerIsm", manifest_args!(rule!(deny_all)));
+    result.0.expect_commit_success();
+    let rejecting_ism = result.1.unwrap();
+    let noop_ism = create_noop_ism(&mut suite);
+
+    let (ism, _) = create_fallback_routing_ism(&mut suite, mailbox, vec![]);
+    let token = create_token(&mut suite, mailbox, ism);
+    let visible_components = vec![suite.account.address, token];
+
+    // The default ISM is looked up while the mailbox processes the message
+    set_default_ism(&mut suite, mailbox, mailbox_owner_badge, rejecting_ism);
+    let receipt = suite.call_method(
+        mailbox,
+        "process",
+        manifest_args!(
+            Vec::<u8>::new(),
+            hex::decode(PAYLOAD).unwrap(),
+            visible_components.clone()
+        ),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Mailbox: ISM verification failed"));
+
+    set_default_ism(&mut suite, mailbox, mailbox_owner_badge, noop_ism);
+    suite
+        .call_method(
+            mailbox,
+            "process",
+            manifest_args!(
+                Vec::<u8>::new(),
+                hex::decode(PAYLOAD).unwrap(),
+                visible_components
+            ),
+        )
+        .expect_commit_success();
+}
+
+#[test]
+fn test_set_route_only_owner() {
+    let mut suite = common::setup();
+    let (mailbox, _) = create_mailbox(&mut suite);
+    let noop_ism = create_noop_ism(&mut suite);
+    let (ism, owner_badge) = create_fallback_routing_ism(&mut suite, mailbox, vec![]);
+
+    let receipt = suite.call_method(ism, "set_route", manifest_args!(17u32, noop_ism));
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("SystemModuleError(AuthError(Unauthorized(Unauthorized"));
+
+    suite
+        .call_method_with_badge(
+            ism,
+            "set_route",
+            owner_badge,
+            manifest_args!(17u32, noop_ism),
+        )
+        .expect_commit_success();
+    let route: ComponentAddress =
+        suite.call_method_success(ism, "route", manifest_args!(raw_message(17)));
+    assert_eq!(route, noop_ism);
+}
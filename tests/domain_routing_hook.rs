@@ -0,0 +1,213 @@
+use crate::common::Suite;
+use hyperlane_radix::types::Bytes32;
+use scrypto_test::prelude::*;
+
+mod common;
+
+fn create_igp(suite: &mut Suite, destination: u32, gas_overhead: u128) -> ComponentAddress {
+    let result = suite.instantiate_blueprint("InterchainGasPaymaster", manifest_args!(XRD));
+    result.0.expect_commit_success();
+    let (igp, owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    let configs = vec![(destination, ((10_000_000_000u128, 1u128), gas_overhead))];
+    suite
+        .call_method_with_badge(
+            igp,
+            "set_destination_gas_configs",
+            owner_badge,
+            manifest_args!(configs),
+        )
+        .expect_commit_success();
+
+    igp
+}
+
+fn setup_mailbox(suite: &mut Suite) -> (ComponentAddress, ResourceAddress) {
+    let result = suite.instantiate_blueprint("Mailbox", manifest_args!(100u32));
+    result.0.expect_commit_success();
+    (result.1.unwrap(), result.2.unwrap())
+}
+
+fn create_domain_routing_hook(
+    suite: &mut Suite,
+    hooks: Vec<(u32, ComponentAddress)>,
+) -> (ComponentAddress, ResourceAddress) {
+    let result = suite.instantiate_blueprint("DomainRoutingHook", manifest_args!(hooks));
+    result.0.expect_commit_success();
+    (result.1.unwrap(), result.2.unwrap())
+}
+
+fn dispatch(
+    suite: &mut Suite,
+    mailbox: ComponentAddress,
+    destination: u32,
+    payment: Decimal,
+) -> TransactionReceipt {
+    let metadata = Some((Decimal::one(), Option::<Vec<u8>>::None));
+    let claimed_account_address = suite.account.address;
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(suite.account.address, XRD, payment)
+        .take_from_worktop(XRD, payment, "payment")
+        .call_method_with_name_lookup(mailbox, "dispatch", |lookup| {
+            manifest_args!(
+                destination,
+                Bytes32::zero(),
+                Vec::<u8>::new(),
+                None::<ComponentAddress>,
+                metadata,
+                vec![lookup.bucket("payment")],
+                ManifestValue::enum_variant(
+                    1u8,
+                    vec![ManifestValue::Custom {
+                        value: ManifestCustomValue::Address(ManifestAddress::Static(
+                            *claimed_account_address.as_node_id()
+                        )),
+                    }]
+                )
+            )
+        })
+        .deposit_batch(suite.account.address, ManifestExpression::EntireWorktop)
+        .build();
+
+    suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    )
+}
+
+fn quote_dispatch(
+    suite: &mut Suite,
+    hook: ComponentAddress,
+    destination: u32,
+) -> TransactionReceipt {
+    let metadata = Some((Decimal::one(), Option::<Vec<u8>>::None));
+    suite.call_method(
+        hook,
+        "quote_dispatch",
+        manifest_args!(
+            metadata,
+            (
+                3u8,
+                0u32,
+                0u32,
+                Bytes32::zero(),
+                destination,
+                Bytes32::zero(),
+                Vec::<u8>::new()
+            )
+        ),
+    )
+}
+
+#[test]
+fn test_route_quote_by_destination() {
+    let mut suite = common::setup();
+    let igp_1 = create_igp(&mut suite, 1337, 10);
+    let igp_2 = create_igp(&mut suite, 1338, 20);
+    let (hook, _) = create_domain_routing_hook(&mut suite, vec![(1337, igp_1), (1338, igp_2)]);
+
+    let configured: Option<ComponentAddress> =
+        suite.call_method_success(hook, "hook", manifest_args!(1338u32));
+    assert_eq!(configured, Some(igp_2));
+
+    let receipt = quote_dispatch(&mut suite, hook, 1337);
+    let call_result = receipt.expect_commit_success().outcome.expect_success();
+    let expected: indexmap::IndexMap<ResourceAddress, Decimal> =
+        IndexMap::from_iter(vec![(XRD, Decimal::from(11))]);
+    call_result[1].expect_return_value(&expected);
+
+    let receipt = quote_dispatch(&mut suite, hook, 1338);
+    let call_result = receipt.expect_commit_success().outcome.expect_success();
+    let expected: indexmap::IndexMap<ResourceAddress, Decimal> =
+        IndexMap::from_iter(vec![(XRD, Decimal::from(21))]);
+    call_result[1].expect_return_value(&expected);
+}
+
+#[test]
+fn test_post_dispatch_routes_payment() {
+    let mut suite = common::setup();
+    let igp_1 = create_igp(&mut suite, 1337, 10);
+    let igp_2 = create_igp(&mut suite, 1338, 20);
+    let (mailbox, mailbox_owner_badge) = setup_mailbox(&mut suite);
+    let (hook, _) = create_domain_routing_hook(&mut suite, vec![(1337, igp_1), (1338, igp_2)]);
+
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_default_hook",
+            mailbox_owner_badge,
+            manifest_args!(hook),
+        )
+        .expect_commit_success();
+
+    // this should consume the entire bucket
+    dispatch(&mut suite, mailbox, 1338, dec!(21)).expect_commit_success();
+
+    assert_eq!(suite.ledger.get_component_balance(igp_1, XRD), dec!(0));
+    assert_eq!(suite.ledger.get_component_balance(igp_2, XRD), dec!(21));
+}
+
+#[test]
+fn test_route_to_aggregation_hook() {
+    let mut suite = common::setup();
+    let igp_1 = create_igp(&mut suite, 1337, 10);
+    let igp_2 = create_igp(&mut suite, 1337, 10);
+    let result = suite.instantiate_blueprint("AggregationHook", manifest_args!(vec![igp_1, igp_2]));
+    result.0.expect_commit_success();
+    let aggregation_hook = result.1.unwrap();
+
+    let (mailbox, mailbox_owner_badge) = setup_mailbox(&mut suite);
+    let (hook, _) = create_domain_routing_hook(&mut suite, vec![(1337, aggregation_hook)]);
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_default_hook",
+            mailbox_owner_badge,
+            manifest_args!(hook),
+        )
+        .expect_commit_success();
+
+    dispatch(&mut suite, mailbox, 1337, dec!(22)).expect_commit_success();
+
+    assert_eq!(suite.ledger.get_component_balance(igp_1, XRD), dec!(11));
+    assert_eq!(suite.ledger.get_component_balance(igp_2, XRD), dec!(11));
+}
+
+#[test]
+fn test_unconfigured_destination() {
+    let mut suite = common::setup();
+    let (hook, _) = create_domain_routing_hook(&mut suite, vec![]);
+
+    let receipt = quote_dispatch(&mut suite, hook, 1337);
+
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("DomainRoutingHook: no hook for destination 1337"));
+}
+
+#[test]
+fn test_set_and_remove_hook() {
+    let mut suite = common::setup();
+    let igp = create_igp(&mut suite, 1337, 10);
+    let (hook, owner_badge) = create_domain_routing_hook(&mut suite, vec![]);
+
+    // Non-owner can not set a hook
+    let receipt = suite.call_method(hook, "set_hook", manifest_args!(1337u32, igp));
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("SystemModuleError(AuthError(Unauthorized(Unauthorized"));
+
+    suite
+        .call_method_with_badge(hook, "set_hook", owner_badge, manifest_args!(1337u32, igp))
+        .expect_commit_success();
+    quote_dispatch(&mut suite, hook, 1337).expect_commit_success();
+
+    suite
+        .call_method_with_badge(hook, "remove_hook", owner_badge, manifest_args!(1337u32))
+        .expect_commit_success();
+    let receipt = quote_dispatch(&mut suite, hook, 1337);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("DomainRoutingHook: no hook for destination 1337"));
+}
@@ -0,0 +1,212 @@
+use crate::common::Suite;
+use hyperlane_radix::types::Bytes32;
+use scrypto_test::prelude::*;
+
+mod common;
+
+fn create_mailbox(suite: &mut Suite, local_domain: u32) -> (ComponentAddress, ResourceAddress) {
+    let result = suite.instantiate_blueprint("Mailbox", manifest_args!(local_domain));
+    result.0.expect_commit_success();
+    (result.1.unwrap(), result.2.unwrap())
+}
+
+fn create_igp(suite: &mut Suite, destination: u32, gas_overhead: u128) -> ComponentAddress {
+    let result = suite.instantiate_blueprint("InterchainGasPaymaster", manifest_args!(XRD));
+    result.0.expect_commit_success();
+    let (igp, owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    let configs = vec![(destination, ((10_000_000_000u128, 1u128), gas_overhead))];
+    suite
+        .call_method_with_badge(
+            igp,
+            "set_destination_gas_configs",
+            owner_badge,
+            manifest_args!(configs),
+        )
+        .expect_commit_success();
+
+    igp
+}
+
+fn create_fallback_routing_hook(
+    suite: &mut Suite,
+    mailbox: ComponentAddress,
+    hooks: Vec<(u32, ComponentAddress)>,
+) -> (ComponentAddress, ResourceAddress) {
+    let result = suite.instantiate_blueprint("FallbackRoutingHook", manifest_args!(mailbox, hooks));
+    result.0.expect_commit_success();
+    (result.1.unwrap(), result.2.unwrap())
+}
+
+fn dispatch_message(
+    suite: &mut Suite,
+    mailbox: ComponentAddress,
+    destination: u32,
+    hook: ComponentAddress,
+    payment: Decimal,
+) -> TransactionReceipt {
+    let claimed_account_address = suite.account.address;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(suite.account.address, XRD, payment)
+        .take_from_worktop(XRD, payment, "payment")
+        .call_method_with_name_lookup(mailbox, "dispatch", |lookup| {
+            manifest_args!(
+                destination,
+                Bytes32::zero(),
+                Vec::<u8>::new(),
+                Some(hook),
+                Some((Decimal::one(), None::<Vec<u8>>)),
+                vec![lookup.bucket("payment")],
+                ManifestValue::enum_variant(
+                    1u8,
+                    vec![ManifestValue::Custom {
+                        value: ManifestCustomValue::Address(ManifestAddress::Static(
+                            *claimed_account_address.as_node_id()
+                        )),
+                    }]
+                )
+            )
+        })
+        .deposit_batch(suite.account.address, ManifestExpression::EntireWorktop)
+        .build();
+
+    suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    )
+}
+
+#[test]
+fn test_fallback_to_mailbox_default_hook() {
+    let mut suite = common::setup();
+    let (mailbox, mailbox_owner_badge) = create_mailbox(&mut suite, 100);
+    let default_igp = create_igp(&mut suite, 1337, 10);
+    let override_igp = create_igp(&mut suite, 1338, 20);
+
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_default_hook",
+            mailbox_owner_badge,
+            manifest_args!(default_igp),
+        )
+        .expect_commit_success();
+
+    let (hook, _) = create_fallback_routing_hook(&mut suite, mailbox, vec![(1338, override_igp)]);
+
+    let fallback: Option<ComponentAddress> =
+        suite.call_method_success(hook, "fallback_hook", manifest_args!());
+    assert_eq!(fallback, Some(default_igp));
+
+    // Unconfigured destination is routed to the mailbox default hook
+    dispatch_message(&mut suite, mailbox, 1337, hook, dec!(11)).expect_commit_success();
+    assert_eq!(
+        suite.ledger.get_component_balance(default_igp, XRD),
+        dec!(11)
+    );
+
+    // Configured destination is routed to the override
+    dispatch_message(&mut suite, mailbox, 1338, hook, dec!(21)).expect_commit_success();
+    assert_eq!(
+        suite.ledger.get_component_balance(override_igp, XRD),
+        dec!(21)
+    );
+}
+
+#[test]
+fn test_no_fallback_configured() {
+    let mut suite = common::setup();
+    let (mailbox, _) = create_mailbox(&mut suite, 100);
+    let (hook, _) = create_fallback_routing_hook(&mut suite, mailbox, vec![]);
+
+    let receipt = dispatch_message(&mut suite, mailbox, 1337, hook, dec!(1));
+
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("FallbackRoutingHook: no hook for destination 1337"));
+}
+
+#[test]
+fn test_fallback_to_itself() {
+    let mut suite = common::setup();
+    let (mailbox, mailbox_owner_badge) = create_mailbox(&mut suite, 100);
+    let (hook, _) = create_fallback_routing_hook(&mut suite, mailbox, vec![]);
+
+    // The routing hook can't fall back to itself
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_default_hook",
+            mailbox_owner_badge,
+            manifest_args!(hook),
+        )
+        .expect_commit_success();
+    let receipt = dispatch_message(&mut suite, mailbox, 1337, hook, dec!(1));
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("FallbackRoutingHook: fallback hook can not be the routing hook itself"));
+}
+
+#[test]
+fn test_fallback_follows_default_hook() {
+    let mut suite = common::setup();
+    let (mailbox, mailbox_owner_badge) = create_mailbox(&mut suite, 100);
+    let old_igp = create_igp(&mut suite, 1337, 10);
+    let new_igp = create_igp(&mut suite, 1337, 10);
+
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_default_hook",
+            mailbox_owner_badge,
+            manifest_args!(old_igp),
+        )
+        .expect_commit_success();
+    let (hook, _) = create_fallback_routing_hook(&mut suite, mailbox, vec![]);
+
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_default_hook",
+            mailbox_owner_badge,
+            manifest_args!(new_igp),
+        )
+        .expect_commit_success();
+
+    let fallback: Option<ComponentAddress> =
+        suite.call_method_success(hook, "fallback_hook", manifest_args!());
+    assert_eq!(fallback, Some(new_igp));
+
+    // The default hook is looked up while the mailbox dispatches the message
+    dispatch_message(&mut suite, mailbox, 1337, hook, dec!(11)).expect_commit_success();
+    assert_eq!(suite.ledger.get_component_balance(old_igp, XRD), dec!(0));
+    assert_eq!(suite.ledger.get_component_balance(new_igp, XRD), dec!(11));
+}
+
+#[test]
+fn test_post_dispatch_without_mailbox() {
+    let mut suite = common::setup();
+    let (mailbox, _) = create_mailbox(&mut suite, 100);
+    let (hook, _) = create_fallback_routing_hook(&mut suite, mailbox, vec![]);
+
+    let metadata = None::<(Decimal, Option<Vec<u8>>)>;
+    let message = (
+        3u8,
+        0u32,
+        0u32,
+        Bytes32::zero(),
+        1337u32,
+        Bytes32::zero(),
+        Vec::<u8>::new(),
+    );
+    let receipt = suite.call_method(
+        hook,
+        "post_dispatch",
+        manifest_args!(metadata, message, Vec::<ManifestBucket>::new()),
+    );
+
+    // The hook only forwards, so it can be nested under other hooks
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("FallbackRoutingHook: no hook for destination 1337"));
+}
@@ -1,6 +1,7 @@
 use crate::common::{hex_str_to_bytes32, Suite};
+use hyperlane_radix::contracts::mailbox::{Delivery, MessageStatus};
 use hyperlane_radix::types::metadata::StandardHookMetadata;
-use hyperlane_radix::types::Bytes32;
+use hyperlane_radix::types::{Bytes32, HyperlaneMessage};
 use scrypto_test::prelude::*;
 
 mod common;
@@ -544,3 +545,320 @@ fn test_mailbox_replay_protection() {
     assert!(format!("{:?}", receipt.expect_commit_failure())
         .contains("Mailbox: message already processed"));
 }
+
+#[test]
+fn test_mailbox_process_batch() {
+    //Arrange
+    let mut suite = common::setup();
+    let mailbox_component = setup_mailbox(&mut suite);
+    let recipient_contract: Bytes32 =
+        hex_str_to_bytes32("0000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496");
+
+    let (synthetic_token, owner_badge, synthetic_token_resource) =
+        create_synthetic_token(&mut suite, mailbox_component, 18);
+
+    suite
+        .call_method_with_badge(
+            synthetic_token,
+            "enroll_remote_router",
+            owner_badge,
+            manifest_args!(1337u32, recipient_contract, dec!(12)),
+        )
+        .expect_commit_success();
+
+    // three transfers of 50 tokens, that only differ in their nonce
+    let metadata: Vec<u8> = vec![];
+    let payload: Vec<u8> = hex::decode("0300000000000005390000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496000003e80000c07341fadfb99d506736cf979374b560851b181d9e83e225d5437ac270e80000c1f7abd48c518b8ebdc6a35abfbe78583725a97eabdc99224571e0d11d42000000000000000000000000000000000000000000000002b5e3af16b1880000").unwrap();
+    let payloads: Vec<Vec<u8>> = (0u8..3)
+        .map(|nonce| {
+            let mut payload = payload.clone();
+            payload[4] = nonce;
+            payload
+        })
+        .collect();
+    let message_ids: Vec<Bytes32> = payloads
+        .iter()
+        .map(|payload| HyperlaneMessage::from(payload.clone()).id())
+        .collect();
+    let visible_components = vec![suite.account.address, synthetic_token];
+    let batch = |payloads: &[Vec<u8>]| -> Vec<(Vec<u8>, Vec<u8>, Vec<ComponentAddress>)> {
+        payloads
+            .iter()
+            .map(|payload| {
+                (
+                    metadata.clone(),
+                    payload.clone(),
+                    visible_components.clone(),
+                )
+            })
+            .collect()
+    };
+
+    // Act
+    let processed: Vec<Bytes32> = suite.call_method_success(
+        mailbox_component,
+        "process_batch",
+        manifest_args!(batch(&payloads[0..2]), false),
+    );
+
+    // Assert
+    assert_eq!(processed, message_ids[0..2].to_vec());
+    let processed_count: u32 =
+        suite.call_method_success(mailbox_component, "processed", manifest_args!());
+    assert_eq!(processed_count, 2);
+
+    // a batch with a delivered message fails, unless delivered messages are skipped
+    let receipt = suite.call_method(
+        mailbox_component,
+        "process_batch",
+        manifest_args!(batch(&payloads), false),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Mailbox: message already processed"));
+
+    let processed: Vec<Bytes32> = suite.call_method_success(
+        mailbox_component,
+        "process_batch",
+        manifest_args!(batch(&payloads), true),
+    );
+    assert_eq!(processed, vec![message_ids[2]]);
+
+    for message_id in message_ids {
+        let delivered: bool =
+            suite.call_method_success(mailbox_component, "delivered", manifest_args!(message_id));
+        assert!(delivered);
+    }
+
+    let account_balance = suite
+        .ledger
+        .get_component_balance(suite.account.address, synthetic_token_resource);
+    assert_eq!(account_balance, dec!(150));
+}
+
+#[test]
+fn test_mailbox_delivery_record() {
+    //Arrange
+    let mut suite = common::setup();
+    let mailbox_component = setup_mailbox(&mut suite);
+    let recipient_contract: Bytes32 =
+        hex_str_to_bytes32("0000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496");
+
+    let (synthetic_token, owner_badge, _synthetic_token_resource) =
+        create_synthetic_token(&mut suite, mailbox_component, 18);
+
+    suite
+        .call_method_with_badge(
+            synthetic_token,
+            "enroll_remote_router",
+            owner_badge,
+            manifest_args!(1337u32, recipient_contract, dec!(12)),
+        )
+        .expect_commit_success();
+
+    let metadata: Vec<u8> = vec![];
+    let payload: Vec<u8> = hex::decode("0300000000000005390000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496000003e80000c07341fadfb99d506736cf979374b560851b181d9e83e225d5437ac270e80000c1f7abd48c518b8ebdc6a35abfbe78583725a97eabdc99224571e0d11d42000000000000000000000000000000000000000000000002b5e3af16b1880000").unwrap();
+    let message_id = HyperlaneMessage::from(payload.clone()).id();
+    let visible_components = vec![suite.account.address, synthetic_token];
+
+    let delivery: Option<Delivery> =
+        suite.call_method_success(mailbox_component, "delivery", manifest_args!(message_id));
+    assert!(delivery.is_none());
+
+    // Act
+    suite
+        .ledger
+        .advance_to_round_at_timestamp(Round::of(1), 1_700_000_000_000);
+    suite
+        .call_method(
+            mailbox_component,
+            "process",
+            manifest_args!(metadata, payload, visible_components),
+        )
+        .expect_commit_success();
+
+    // Assert
+    let delivery: Delivery = suite
+        .call_method_success::<Option<Delivery>>(
+            mailbox_component,
+            "delivery",
+            manifest_args!(message_id),
+        )
+        .unwrap();
+    assert_eq!(delivery.sequence, 0);
+    assert_eq!(delivery.timestamp, 1_700_000_000);
+    assert_eq!(delivery.epoch, suite.ledger.get_current_epoch().number());
+    assert_ne!(delivery.transaction_hash, Hash([0u8; 32]));
+}
+
+#[test]
+fn test_mailbox_process_requirements() {
+    //Arrange
+    let mut suite = common::setup();
+    let mailbox_component = setup_mailbox(&mut suite);
+    let recipient_contract: Bytes32 =
+        hex_str_to_bytes32("0000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496");
+
+    let (synthetic_token, owner_badge, synthetic_token_resource) =
+        create_synthetic_token(&mut suite, mailbox_component, 18);
+
+    suite
+        .call_method_with_badge(
+            synthetic_token,
+            "enroll_remote_router",
+            owner_badge,
+            manifest_args!(1337u32, recipient_contract, dec!(12)),
+        )
+        .expect_commit_success();
+
+    let metadata: Vec<u8> = vec![];
+    let payload: Vec<u8> = hex::decode("0300000000000005390000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496000003e80000c07341fadfb99d506736cf979374b560851b181d9e83e225d5437ac270e80000c1f7abd48c518b8ebdc6a35abfbe78583725a97eabdc99224571e0d11d42000000000000000000000000000000000000000000000002b5e3af16b1880000").unwrap();
+
+    // Act
+    let required_components: Vec<ComponentAddress> = suite.call_method_success(
+        mailbox_component,
+        "process_requirements",
+        manifest_args!(payload.clone()),
+    );
+
+    // Assert
+    assert_eq!(required_components, vec![suite.account.address]);
+
+    let receipt = suite.call_method(
+        mailbox_component,
+        "process",
+        manifest_args!(metadata, payload, required_components),
+    );
+    receipt.expect_commit_success();
+
+    let account_balance = suite
+        .ledger
+        .get_component_balance(suite.account.address, synthetic_token_resource);
+    assert_eq!(account_balance, dec!(50));
+}
+
+#[test]
+fn test_mailbox_two_phase_delivery() {
+    //Arrange
+    let mut suite = common::setup();
+    let (mailbox_component, mailbox_owner_badge) = create_mailbox(&mut suite, 1000);
+    let noop_ism = create_noop_ism(&mut suite);
+    suite
+        .call_method_with_badge(
+            mailbox_component,
+            "set_default_ism",
+            mailbox_owner_badge,
+            manifest_args!(noop_ism),
+        )
+        .expect_commit_success();
+
+    let receipt = suite.call_method(
+        mailbox_component,
+        "set_two_phase_delivery",
+        manifest_args!(true),
+    );
+    receipt.expect_auth_failure();
+    suite
+        .call_method_with_badge(
+            mailbox_component,
+            "set_two_phase_delivery",
+            mailbox_owner_badge,
+            manifest_args!(true),
+        )
+        .expect_commit_success();
+
+    let recipient_contract: Bytes32 =
+        hex_str_to_bytes32("0000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496");
+    let (synthetic_token, owner_badge, synthetic_token_resource) =
+        create_synthetic_token(&mut suite, mailbox_component, 18);
+    suite
+        .call_method_with_badge(
+            synthetic_token,
+            "enroll_remote_router",
+            owner_badge,
+            manifest_args!(1337u32, recipient_contract, dec!(12)),
+        )
+        .expect_commit_success();
+
+    let metadata: Vec<u8> = vec![];
+    let payload: Vec<u8> = hex::decode("0300000000000005390000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496000003e80000c07341fadfb99d506736cf979374b560851b181d9e83e225d5437ac270e80000c1f7abd48c518b8ebdc6a35abfbe78583725a97eabdc99224571e0d11d42000000000000000000000000000000000000000000000002b5e3af16b1880000").unwrap();
+    let message_id = HyperlaneMessage::from(payload.clone()).id();
+
+    // Act - the handle would fail without visible components, but is not called yet
+    suite
+        .call_method(
+            mailbox_component,
+            "process",
+            manifest_args!(
+                metadata.clone(),
+                payload.clone(),
+                Vec::<ComponentAddress>::new()
+            ),
+        )
+        .expect_commit_success();
+
+    // Assert
+    let status: MessageStatus = suite.call_method_success(
+        mailbox_component,
+        "message_status",
+        manifest_args!(message_id),
+    );
+    assert_eq!(status, MessageStatus::Verified);
+    let delivered: bool =
+        suite.call_method_success(mailbox_component, "delivered", manifest_args!(message_id));
+    assert!(!delivered);
+
+    let receipt = suite.call_method(
+        mailbox_component,
+        "process",
+        manifest_args!(metadata, payload, Vec::<ComponentAddress>::new()),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Mailbox: message already processed"));
+
+    // a failed execution keeps the message verified
+    let receipt = suite.call_method(
+        mailbox_component,
+        "execute",
+        manifest_args!(message_id, Vec::<ComponentAddress>::new()),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure()).contains("RequiredAddresses"));
+    let status: MessageStatus = suite.call_method_success(
+        mailbox_component,
+        "message_status",
+        manifest_args!(message_id),
+    );
+    assert_eq!(status, MessageStatus::Verified);
+
+    // anyone can retry the execution
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            mailbox_component,
+            "execute",
+            manifest_args!(message_id, vec![suite.account.address]),
+        )
+        .build();
+    suite
+        .ledger
+        .execute_manifest(manifest, vec![])
+        .expect_commit_success();
+
+    let status: MessageStatus = suite.call_method_success(
+        mailbox_component,
+        "message_status",
+        manifest_args!(message_id),
+    );
+    assert_eq!(status, MessageStatus::Executed);
+    let account_balance = suite
+        .ledger
+        .get_component_balance(suite.account.address, synthetic_token_resource);
+    assert_eq!(account_balance, dec!(50));
+
+    let receipt = suite.call_method(
+        mailbox_component,
+        "execute",
+        manifest_args!(message_id, vec![suite.account.address]),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Mailbox: message is not verified or already executed"));
+}
@@ -0,0 +1,170 @@
+use crate::common::Suite;
+use hyperlane_radix::contracts::isms::types::Types;
+use hyperlane_radix::types::EthAddress;
+use scrypto_test::prelude::*;
+
+mod common;
+
+const ROUTING: u8 = 1;
+const MERKLE_ROOT_MULTISIG: u8 = 4;
+const MESSAGE_ID_MULTISIG: u8 = 5;
+
+/// "Types" is not manifest encodable, so the variant is passed by its discriminator
+fn module_type(discriminator: u8) -> ManifestValue {
+    ManifestValue::Enum {
+        discriminator,
+        fields: vec![],
+    }
+}
+
+fn create_ism_factory(suite: &mut Suite) -> ComponentAddress {
+    let result = suite.instantiate_blueprint("IsmFactory", manifest_args!());
+    result.0.expect_commit_success();
+    result.1.unwrap()
+}
+
+fn deploy(
+    suite: &mut Suite,
+    factory: ComponentAddress,
+    discriminator: u8,
+    validators: Vec<EthAddress>,
+    threshold: usize,
+) -> ComponentAddress {
+    suite.call_method_success(
+        factory,
+        "deploy",
+        manifest_args!(module_type(discriminator), validators, threshold),
+    )
+}
+
+#[test]
+fn test_deploy_reuses_ism() {
+    let mut suite = common::setup();
+    let factory = create_ism_factory(&mut suite);
+
+    let validators: Vec<EthAddress> = vec![[1u8; 20].into(), [2u8; 20].into()];
+    let ism = deploy(
+        &mut suite,
+        factory,
+        MESSAGE_ID_MULTISIG,
+        validators.clone(),
+        2,
+    );
+
+    // the validators are sorted before hashing
+    let unsorted: Vec<EthAddress> = validators.iter().rev().cloned().collect();
+    let reused = deploy(&mut suite, factory, MESSAGE_ID_MULTISIG, unsorted, 2);
+    assert_eq!(reused, ism);
+
+    let deployed: Option<ComponentAddress> = suite.call_method_success(
+        factory,
+        "deployed_ism",
+        manifest_args!(module_type(MESSAGE_ID_MULTISIG), validators.clone(), 2usize),
+    );
+    assert_eq!(deployed, Some(ism));
+
+    let ism_type: Types = suite.call_method_success(ism, "module_type", manifest_args!());
+    assert!(matches!(ism_type, Types::MessageIdMultisig));
+
+    let validators_and_threshold: (Vec<EthAddress>, usize) = suite.call_method_success(
+        ism,
+        "validators_and_threshold",
+        manifest_args!(Vec::<u8>::new()),
+    );
+    assert_eq!(validators_and_threshold, (validators, 2));
+}
+
+#[test]
+fn test_deploy_distinguishes_configurations() {
+    let mut suite = common::setup();
+    let factory = create_ism_factory(&mut suite);
+
+    let validators: Vec<EthAddress> = vec![[1u8; 20].into(), [2u8; 20].into()];
+    let message_id_ism = deploy(
+        &mut suite,
+        factory,
+        MESSAGE_ID_MULTISIG,
+        validators.clone(),
+        2,
+    );
+    let merkle_root_ism = deploy(
+        &mut suite,
+        factory,
+        MERKLE_ROOT_MULTISIG,
+        validators.clone(),
+        2,
+    );
+    let lower_threshold_ism = deploy(
+        &mut suite,
+        factory,
+        MESSAGE_ID_MULTISIG,
+        validators.clone(),
+        1,
+    );
+
+    assert_ne!(message_id_ism, merkle_root_ism);
+    assert_ne!(message_id_ism, lower_threshold_ism);
+
+    let ism_type: Types =
+        suite.call_method_success(merkle_root_ism, "module_type", manifest_args!());
+    assert!(matches!(ism_type, Types::MerkleRootMultisig));
+
+    let deployed: Option<ComponentAddress> = suite.call_method_success(
+        factory,
+        "deployed_ism",
+        manifest_args!(module_type(MERKLE_ROOT_MULTISIG), validators, 1usize),
+    );
+    assert_eq!(deployed, None);
+}
+
+#[test]
+fn test_deploy_invalid_configuration() {
+    let mut suite = common::setup();
+    let factory = create_ism_factory(&mut suite);
+
+    let receipt = suite.call_method(
+        factory,
+        "deploy",
+        manifest_args!(module_type(ROUTING), Vec::<EthAddress>::new(), 0usize),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("IsmFactory: only multisig ISMs can be deployed"));
+
+    let duplicates: Vec<EthAddress> = vec![[1u8; 20].into(), [1u8; 20].into()];
+    let receipt = suite.call_method(
+        factory,
+        "deploy",
+        manifest_args!(module_type(MESSAGE_ID_MULTISIG), duplicates, 1usize),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: validators must be sorted in ascending order without duplicates"));
+
+    let validators: Vec<EthAddress> = vec![[1u8; 20].into()];
+    let receipt = suite.call_method(
+        factory,
+        "deploy",
+        manifest_args!(module_type(MESSAGE_ID_MULTISIG), validators.clone(), 2usize),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: threshold must be less than or equal to the number of validators"));
+
+    let receipt = suite.call_method(
+        factory,
+        "deploy",
+        manifest_args!(module_type(MESSAGE_ID_MULTISIG), validators, 0usize),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: threshold must be positive"));
+
+    let receipt = suite.call_method(
+        factory,
+        "deploy",
+        manifest_args!(
+            module_type(MERKLE_ROOT_MULTISIG),
+            Vec::<EthAddress>::new(),
+            0usize
+        ),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("IsmFactory: at least one validator is required"));
+}
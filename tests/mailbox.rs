@@ -1,4 +1,5 @@
 use crate::common::Suite;
+use hyperlane_radix::contracts::mailbox::DispatchStorage;
 use hyperlane_radix::types::{Bytes32, HyperlaneMessage};
 use scrypto_test::prelude::*;
 
@@ -414,3 +415,120 @@ fn test_required_hook() {
         "post_dispatch required hook was not called"
     )
 }
+
+fn set_dispatch_storage(
+    suite: &mut Suite,
+    mailbox: ComponentAddress,
+    owner_badge: ResourceAddress,
+    variant: u8,
+) {
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_dispatch_storage",
+            owner_badge,
+            manifest_args!(ManifestValue::enum_variant(variant, vec![])),
+        )
+        .expect_commit_success();
+}
+
+#[test]
+fn test_dispatch_storage() {
+    let mut suite = common::setup();
+    let (receipt, mailbox, owner_badge) = create_mailbox(&mut suite, 100);
+    receipt.expect_commit_success();
+    let (mailbox, owner_badge) = (mailbox.unwrap(), owner_badge.unwrap());
+    let address = suite.account.address;
+
+    let storage: DispatchStorage =
+        suite.call_method_success(mailbox, "dispatch_storage", manifest_args!());
+    assert_eq!(storage, DispatchStorage::None);
+
+    // nonce 0 - nothing is stored
+    dispatch_message(
+        &mut suite,
+        mailbox,
+        1337u32,
+        Bytes32::zero(),
+        vec![0],
+        None,
+        address,
+        dec!(200000),
+    )
+    .expect_commit_success();
+
+    // nonce 1 - only the id is stored
+    set_dispatch_storage(&mut suite, mailbox, owner_badge, 1);
+    dispatch_message(
+        &mut suite,
+        mailbox,
+        1337u32,
+        Bytes32::zero(),
+        vec![1],
+        None,
+        address,
+        dec!(200000),
+    )
+    .expect_commit_success();
+    let id_1: Bytes32 =
+        suite.call_method_success(mailbox, "latest_dispatched_id", manifest_args!());
+
+    // nonce 2 - the message is stored
+    set_dispatch_storage(&mut suite, mailbox, owner_badge, 2);
+    let storage: DispatchStorage =
+        suite.call_method_success(mailbox, "dispatch_storage", manifest_args!());
+    assert_eq!(storage, DispatchStorage::Messages);
+    dispatch_message(
+        &mut suite,
+        mailbox,
+        1337u32,
+        Bytes32::zero(),
+        vec![2],
+        None,
+        address,
+        dec!(200000),
+    )
+    .expect_commit_success();
+    let id_2: Bytes32 =
+        suite.call_method_success(mailbox, "latest_dispatched_id", manifest_args!());
+
+    let dispatched_id: Option<Bytes32> =
+        suite.call_method_success(mailbox, "dispatched_id", manifest_args!(0u32));
+    assert_eq!(dispatched_id, None);
+    let dispatched_id: Option<Bytes32> =
+        suite.call_method_success(mailbox, "dispatched_id", manifest_args!(1u32));
+    assert_eq!(dispatched_id, Some(id_1));
+    let dispatched_id: Option<Bytes32> =
+        suite.call_method_success(mailbox, "dispatched_id", manifest_args!(2u32));
+    assert_eq!(dispatched_id, Some(id_2));
+
+    let dispatched_message: Option<HyperlaneMessage> =
+        suite.call_method_success(mailbox, "dispatched_message", manifest_args!(1u32));
+    assert_eq!(dispatched_message, None);
+    let dispatched_message: HyperlaneMessage = suite
+        .call_method_success::<Option<HyperlaneMessage>>(
+            mailbox,
+            "dispatched_message",
+            manifest_args!(2u32),
+        )
+        .unwrap();
+    assert_eq!(dispatched_message.nonce, 2);
+    assert_eq!(dispatched_message.origin, 100);
+    assert_eq!(dispatched_message.destination, 1337);
+    assert_eq!(dispatched_message.body, vec![2]);
+    assert_eq!(dispatched_message.id(), id_2);
+}
+
+#[test]
+fn test_set_dispatch_storage_only_owner() {
+    let mut suite = common::setup();
+    let (receipt, mailbox, _) = create_mailbox(&mut suite, 100);
+    receipt.expect_commit_success();
+
+    let receipt = suite.call_method(
+        mailbox.unwrap(),
+        "set_dispatch_storage",
+        manifest_args!(ManifestValue::enum_variant(2u8, vec![])),
+    );
+    receipt.expect_auth_failure();
+}
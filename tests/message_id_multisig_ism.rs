@@ -1,6 +1,6 @@
 use crate::common::Suite;
 use hyperlane_radix::types::metadata::MultisigIsmMessageIdMetadata;
-use hyperlane_radix::types::EthAddress;
+use hyperlane_radix::types::{Bytes32, EthAddress, HyperlaneMessage};
 use scrypto_test::prelude::*;
 
 mod common;
@@ -138,3 +138,418 @@ fn test_invalid_relayer_message() {
     assert!(format!("{:?}", receipt.expect_commit_failure())
         .contains("Multisig: threshold not reached"));
 }
+
+fn create_owned_message_id_multisig_ism(
+    suite: &mut Suite,
+    validators: Vec<EthAddress>,
+    threshold: usize,
+) -> TransactionReceipt {
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            suite.package_address,
+            "MessageIdMultisigIsm",
+            "instantiate_with_owner",
+            manifest_args!(validators, threshold),
+        )
+        .deposit_entire_worktop(suite.account.address)
+        .build();
+
+    suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    )
+}
+
+#[test]
+fn test_set_validators_and_threshold() {
+    // Arrange
+    let message = hex::decode("0300000000000000010000000000000000000000007ff2bf58c38a41ad7c9cbc14e780e8a7edbbd48d00002105000000000000000000000000811808dd29ba8b0fc6c0ec0b5537035e5974516248656c6c6f21").unwrap();
+
+    let validator: [u8; 20] = hex::decode("03c842db86a6a3e524d4a6615390c1ea8e2b9541")
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    let metadata: MultisigIsmMessageIdMetadata = MultisigIsmMessageIdMetadata {
+        origin_merkle_tree_hook: Hash::from_str("00000000000000000000000048e6c30b97748d1e2e03bf3e9fbe3890ca5f8cca").unwrap().into(),
+        merkle_root: Hash::from_str("db278688f4f929bb03c76e57866ca41290dc63a1069752507fe6d20f307f1538").unwrap().into(),
+        merkle_index: 0,
+        validator_signatures: vec![Secp256k1Signature::from_str("3aeb79d0e542b8363144fe5286b1f8f6392d75d3220d9eca0ac20bb0cd41236d0e5eafcce7e6105cc282caa68ce73d095f80f111cde5a8f13e80bd8ddb0b91271b").unwrap()],
+    };
+
+    let mut suite = common::setup();
+    let receipt = create_owned_message_id_multisig_ism(&mut suite, vec![[1u8; 20].into()], 1);
+    let component_address = receipt.expect_commit_success().new_component_addresses()[0];
+    let owner_badge = receipt.expect_commit_success().new_resource_addresses()[0];
+
+    // The initial validator did not sign the message
+    let receipt = verify(
+        &mut suite,
+        component_address,
+        metadata_to_vec(&metadata),
+        message.clone(),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: threshold not reached"));
+
+    // Act
+    let validators: Vec<EthAddress> = vec![validator.into(), [255u8; 20].into()];
+    suite
+        .call_method_with_badge(
+            component_address,
+            "set_validators_and_threshold",
+            owner_badge,
+            manifest_args!(validators.clone(), 1usize),
+        )
+        .expect_commit_success();
+
+    // Assert
+    let validators_and_threshold: (Vec<EthAddress>, usize) = suite.call_method_success(
+        component_address,
+        "validators_and_threshold",
+        manifest_args!(message.clone()),
+    );
+    assert_eq!(validators_and_threshold, (validators, 1));
+
+    let receipt = verify(
+        &mut suite,
+        component_address,
+        metadata_to_vec(&metadata),
+        message,
+    );
+    let call_result = receipt.expect_commit_success().outcome.expect_success();
+    call_result[1].expect_return_value(&true)
+}
+
+#[test]
+fn test_set_invalid_validators_and_threshold() {
+    let mut suite = common::setup();
+    let receipt = create_owned_message_id_multisig_ism(&mut suite, vec![[1u8; 20].into()], 1);
+    let component_address = receipt.expect_commit_success().new_component_addresses()[0];
+    let owner_badge = receipt.expect_commit_success().new_resource_addresses()[0];
+
+    // Non-owner can not rotate the validators
+    let receipt = suite.call_method(
+        component_address,
+        "set_validators_and_threshold",
+        manifest_args!(vec![EthAddress::from([2u8; 20])], 1usize),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("SystemModuleError(AuthError(Unauthorized(Unauthorized"));
+
+    let receipt = suite.call_method_with_badge(
+        component_address,
+        "set_validators_and_threshold",
+        owner_badge,
+        manifest_args!(vec![EthAddress::from([2u8; 20])], 2usize),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: threshold must be less than or equal to the number of validators"));
+
+    let receipt = suite.call_method_with_badge(
+        component_address,
+        "set_validators_and_threshold",
+        owner_badge,
+        manifest_args!(vec![EthAddress::from([2u8; 20])], 0usize),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: threshold must be positive"));
+
+    let unsorted: Vec<EthAddress> = vec![[2u8; 20].into(), [1u8; 20].into()];
+    let receipt = suite.call_method_with_badge(
+        component_address,
+        "set_validators_and_threshold",
+        owner_badge,
+        manifest_args!(unsorted, 1usize),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: validators must be sorted in ascending order without duplicates"));
+}
+
+#[test]
+fn test_static_ism_can_not_be_updated() {
+    let mut suite = common::setup();
+    let receipt = create_message_id_multisig_ism(&mut suite, vec![[1u8; 20].into()], 1);
+    let component_address = receipt.expect_commit_success().new_component_addresses()[0];
+
+    let receipt = suite.call_method(
+        component_address,
+        "set_validators_and_threshold",
+        manifest_args!(vec![EthAddress::from([2u8; 20])], 1usize),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("SystemModuleError(AuthError(Unauthorized(Unauthorized"));
+}
+
+struct Validator {
+    private_key: Secp256k1PrivateKey,
+    public_key: Secp256k1UncompressedPublicKey,
+    address: EthAddress,
+}
+
+/// Validators sorted by their ethereum address
+fn validators(count: u64) -> Vec<Validator> {
+    let digest = hash("public key");
+    let mut validators: Vec<Validator> = (1..=count)
+        .map(|i| {
+            let private_key = Secp256k1PrivateKey::from_u64(i).unwrap();
+            let public_key =
+                verify_and_recover_secp256k1_uncompressed(&digest, &private_key.sign(&digest))
+                    .unwrap();
+            let address = keccak256_hash(&public_key.0[1..]).into();
+            Validator {
+                private_key,
+                public_key,
+                address,
+            }
+        })
+        .collect();
+    validators.sort_by_key(|validator| validator.address);
+    validators
+}
+
+fn signed_message(signers: &[&Validator]) -> (Vec<u8>, Vec<u8>) {
+    let message = HyperlaneMessage {
+        version: 3,
+        nonce: 0,
+        origin: 1337,
+        sender: Default::default(),
+        destination: 0,
+        recipient: Default::default(),
+        body: vec![1, 2, 3],
+    };
+    let origin_merkle_tree_hook: Bytes32 = [1u8; 32].into();
+    let merkle_root: Bytes32 = [2u8; 32].into();
+    let digest = message.digest(origin_merkle_tree_hook, merkle_root, 0);
+
+    // convert the signatures into the ethereum format
+    let validator_signatures = signers
+        .iter()
+        .map(|signer| {
+            let signature = signer.private_key.sign(&digest).to_vec();
+            let mut eth_signature = signature[1..].to_vec();
+            eth_signature.push(signature[0] + 27);
+            Secp256k1Signature(eth_signature.try_into().unwrap())
+        })
+        .collect();
+
+    let metadata = MultisigIsmMessageIdMetadata {
+        origin_merkle_tree_hook,
+        merkle_root,
+        merkle_index: 0,
+        validator_signatures,
+    };
+
+    (metadata_to_vec(&metadata), message.into())
+}
+
+fn create_owned_ism_with_validators(
+    suite: &mut Suite,
+    validators: &[Validator],
+    threshold: usize,
+) -> (ComponentAddress, ResourceAddress) {
+    let addresses: Vec<EthAddress> = validators.iter().map(|v| v.address).collect();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            suite.package_address,
+            "MessageIdMultisigIsm",
+            "instantiate_with_owner",
+            manifest_args!(addresses, threshold),
+        )
+        .deposit_entire_worktop(suite.account.address)
+        .build();
+    let receipt = suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    );
+    let component_address = receipt.expect_commit_success().new_component_addresses()[0];
+    let owner_badge = receipt.expect_commit_success().new_resource_addresses()[0];
+    (component_address, owner_badge)
+}
+
+#[test]
+fn test_verify_with_public_keys() {
+    let mut suite = common::setup();
+    let validators = validators(3);
+    let (ism, owner_badge) = create_owned_ism_with_validators(&mut suite, &validators, 2);
+
+    let public_keys: Vec<Secp256k1UncompressedPublicKey> =
+        validators.iter().map(|v| v.public_key).collect();
+    suite
+        .call_method_with_badge(
+            ism,
+            "set_validator_public_keys",
+            owner_badge,
+            manifest_args!(public_keys),
+        )
+        .expect_commit_success();
+
+    let keys: Option<Vec<Secp256k1PublicKey>> =
+        suite.call_method_success(ism, "validator_public_keys", manifest_args!());
+    let expected: Vec<Secp256k1PublicKey> = validators
+        .iter()
+        .map(|v| v.private_key.public_key())
+        .collect();
+    assert_eq!(keys, Some(expected));
+
+    // signatures of any subset of the validators, ordered like the validators
+    let (metadata, message) = signed_message(&[&validators[0], &validators[2]]);
+    let verified: bool =
+        suite.call_method_success(ism, "verify", manifest_args!(metadata, message));
+    assert!(verified);
+
+    // signatures in the wrong order
+    let (metadata, message) = signed_message(&[&validators[2], &validators[0]]);
+    let receipt = suite.call_method(ism, "verify", manifest_args!(metadata, message));
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: threshold not reached"));
+}
+
+#[test]
+fn test_set_validator_public_keys_must_match_validators() {
+    let mut suite = common::setup();
+    let validators = validators(2);
+    let (ism, owner_badge) = create_owned_ism_with_validators(&mut suite, &validators, 1);
+
+    let reversed: Vec<Secp256k1UncompressedPublicKey> =
+        validators.iter().rev().map(|v| v.public_key).collect();
+    let receipt = suite.call_method_with_badge(
+        ism,
+        "set_validator_public_keys",
+        owner_badge,
+        manifest_args!(reversed),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: public key at 0 does not match its validator"));
+
+    let receipt = suite.call_method_with_badge(
+        ism,
+        "set_validator_public_keys",
+        owner_badge,
+        manifest_args!(vec![validators[0].public_key]),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: expected one public key per validator"));
+
+    let public_keys: Vec<Secp256k1UncompressedPublicKey> =
+        validators.iter().map(|v| v.public_key).collect();
+    let receipt = suite.call_method(
+        ism,
+        "set_validator_public_keys",
+        manifest_args!(public_keys.clone()),
+    );
+    receipt.expect_auth_failure();
+
+    // updating the validator set removes the keys
+    suite
+        .call_method_with_badge(
+            ism,
+            "set_validator_public_keys",
+            owner_badge,
+            manifest_args!(public_keys),
+        )
+        .expect_commit_success();
+    let addresses: Vec<EthAddress> = validators.iter().map(|v| v.address).collect();
+    suite
+        .call_method_with_badge(
+            ism,
+            "set_validators_and_threshold",
+            owner_badge,
+            manifest_args!(addresses, 2usize),
+        )
+        .expect_commit_success();
+
+    let keys: Option<Vec<Secp256k1PublicKey>> =
+        suite.call_method_success(ism, "validator_public_keys", manifest_args!());
+    assert_eq!(keys, None);
+}
+
+#[test]
+fn test_public_keys_reduce_verification_cost() {
+    let mut suite = common::setup();
+    let validators = validators(3);
+    let (address_ism, _) = create_owned_ism_with_validators(&mut suite, &validators, 2);
+    let (key_ism, owner_badge) = create_owned_ism_with_validators(&mut suite, &validators, 2);
+
+    let public_keys: Vec<Secp256k1UncompressedPublicKey> =
+        validators.iter().map(|v| v.public_key).collect();
+    suite
+        .call_method_with_badge(
+            key_ism,
+            "set_validator_public_keys",
+            owner_badge,
+            manifest_args!(public_keys),
+        )
+        .expect_commit_success();
+
+    // cheaper for every signer set, not only if the first validators signed
+    for signers in [[0, 1], [0, 2], [1, 2]] {
+        let signers: Vec<&Validator> = signers.iter().map(|i| &validators[*i]).collect();
+        let (metadata, message) = signed_message(&signers);
+
+        let address_receipt = verify(&mut suite, address_ism, metadata.clone(), message.clone());
+        let key_receipt = verify(&mut suite, key_ism, metadata, message);
+        address_receipt.expect_commit_success();
+        key_receipt.expect_commit_success();
+
+        assert!(
+            key_receipt.fee_summary.total_execution_cost_units_consumed
+                < address_receipt
+                    .fee_summary
+                    .total_execution_cost_units_consumed
+        );
+    }
+}
+
+fn instantiate_with_public_keys(
+    suite: &mut Suite,
+    addresses: Vec<EthAddress>,
+    public_keys: Vec<Secp256k1UncompressedPublicKey>,
+) -> TransactionReceipt {
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            suite.package_address,
+            "MessageIdMultisigIsm",
+            "instantiate_with_public_keys",
+            manifest_args!(addresses, 2usize, public_keys),
+        )
+        .build();
+    suite.ledger.execute_manifest(manifest, vec![])
+}
+
+#[test]
+fn test_instantiate_with_public_keys() {
+    let mut suite = common::setup();
+    let validators = validators(3);
+    let addresses: Vec<EthAddress> = validators.iter().map(|v| v.address).collect();
+    let public_keys: Vec<Secp256k1UncompressedPublicKey> =
+        validators.iter().map(|v| v.public_key).collect();
+
+    let reversed: Vec<Secp256k1UncompressedPublicKey> = public_keys.iter().rev().cloned().collect();
+    let receipt = instantiate_with_public_keys(&mut suite, addresses.clone(), reversed);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Multisig: public key at 0 does not match its validator"));
+
+    let receipt = instantiate_with_public_keys(&mut suite, addresses, public_keys);
+    let ism = receipt.expect_commit_success().new_component_addresses()[0];
+
+    let keys: Option<Vec<Secp256k1PublicKey>> =
+        suite.call_method_success(ism, "validator_public_keys", manifest_args!());
+    let expected: Vec<Secp256k1PublicKey> = validators
+        .iter()
+        .map(|v| v.private_key.public_key())
+        .collect();
+    assert_eq!(keys, Some(expected));
+
+    let (metadata, message) = signed_message(&[&validators[1], &validators[2]]);
+    let verified: bool =
+        suite.call_method_success(ism, "verify", manifest_args!(metadata, message));
+    assert!(verified);
+}
@@ -0,0 +1,173 @@
+use crate::common::Suite;
+use hyperlane_radix::types::HyperlaneMessage;
+use scrypto_test::prelude::*;
+
+mod common;
+
+const FRAUD_WINDOW: i64 = 3_600;
+
+fn create_noop_ism(suite: &mut Suite) -> ComponentAddress {
+    let result = suite.instantiate_blueprint("NoopIsm", manifest_args!());
+    result.0.expect_commit_success();
+    result.1.unwrap()
+}
+
+fn create_optimistic_ism(
+    suite: &mut Suite,
+    submodule: ComponentAddress,
+) -> (ComponentAddress, ResourceAddress, ResourceAddress) {
+    let watcher_badge =
+        suite
+            .ledger
+            .create_fungible_resource(dec!(1), DIVISIBILITY_NONE, suite.account.address);
+
+    let result = suite.instantiate_blueprint(
+        "OptimisticIsm",
+        manifest_args!(submodule, FRAUD_WINDOW, vec![watcher_badge]),
+    );
+    result.0.expect_commit_success();
+    (result.1.unwrap(), result.2.unwrap(), watcher_badge)
+}
+
+fn raw_message(nonce: u32) -> Vec<u8> {
+    HyperlaneMessage {
+        version: 3,
+        nonce,
+        origin: 1337,
+        sender: Default::default(),
+        destination: 0,
+        recipient: Default::default(),
+        body: vec![],
+    }
+    .into()
+}
+
+fn pre_verify(suite: &mut Suite, ism: ComponentAddress, nonce: u32) -> TransactionReceipt {
+    suite.call_method(
+        ism,
+        "pre_verify",
+        manifest_args!(Vec::<u8>::new(), raw_message(nonce)),
+    )
+}
+
+fn verify(suite: &mut Suite, ism: ComponentAddress, nonce: u32) -> TransactionReceipt {
+    suite.call_method(
+        ism,
+        "verify",
+        manifest_args!(Vec::<u8>::new(), raw_message(nonce)),
+    )
+}
+
+#[test]
+fn test_verify_after_fraud_window() {
+    let mut suite = common::setup();
+    let submodule = create_noop_ism(&mut suite);
+    let (ism, _, _) = create_optimistic_ism(&mut suite, submodule);
+
+    let receipt = verify(&mut suite, ism, 0);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("OptimisticIsm: message has not been pre-verified"));
+
+    suite.ledger.advance_to_round_at_timestamp(Round::of(1), 0);
+    pre_verify(&mut suite, ism, 0).expect_commit_success();
+
+    let receipt = pre_verify(&mut suite, ism, 0);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("OptimisticIsm: message already pre-verified"));
+
+    let receipt = verify(&mut suite, ism, 0);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("OptimisticIsm: fraud window has not passed"));
+
+    suite
+        .ledger
+        .advance_to_round_at_timestamp(Round::of(2), FRAUD_WINDOW * 1_000);
+    let receipt = verify(&mut suite, ism, 0);
+    let call_result = receipt.expect_commit_success().outcome.expect_success();
+    call_result[1].expect_return_value(&true);
+}
+
+#[test]
+fn test_flagged_submodule() {
+    let mut suite = common::setup();
+    let submodule = create_noop_ism(&mut suite);
+    let (ism, owner_badge, watcher_badge) = create_optimistic_ism(&mut suite, submodule);
+
+    suite.ledger.advance_to_round_at_timestamp(Round::of(1), 0);
+    pre_verify(&mut suite, ism, 0).expect_commit_success();
+
+    // Only watchers can flag a submodule
+    let receipt = suite.call_method(ism, "flag_submodule", manifest_args!(submodule));
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("SystemModuleError(AuthError(Unauthorized(Unauthorized"));
+
+    suite
+        .call_method_with_badge(
+            ism,
+            "flag_submodule",
+            watcher_badge,
+            manifest_args!(submodule),
+        )
+        .expect_commit_success();
+
+    suite
+        .ledger
+        .advance_to_round_at_timestamp(Round::of(2), FRAUD_WINDOW * 1_000);
+    let receipt = verify(&mut suite, ism, 0);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("OptimisticIsm: submodule has been flagged as fraudulent"));
+
+    let receipt = pre_verify(&mut suite, ism, 1);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("OptimisticIsm: submodule has been flagged as fraudulent"));
+
+    // The owner replaces the compromised submodule
+    let new_submodule = create_noop_ism(&mut suite);
+    suite
+        .call_method_with_badge(
+            ism,
+            "set_submodule",
+            owner_badge,
+            manifest_args!(new_submodule),
+        )
+        .expect_commit_success();
+    pre_verify(&mut suite, ism, 1).expect_commit_success();
+
+    // Messages pre-verified by the flagged submodule are pre-verified again
+    pre_verify(&mut suite, ism, 0).expect_commit_success();
+    let receipt = pre_verify(&mut suite, ism, 0);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("OptimisticIsm: message already pre-verified"));
+
+    let receipt = verify(&mut suite, ism, 0);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("OptimisticIsm: fraud window has not passed"));
+
+    suite
+        .ledger
+        .advance_to_round_at_timestamp(Round::of(3), 2 * FRAUD_WINDOW * 1_000);
+    let receipt = verify(&mut suite, ism, 0);
+    let call_result = receipt.expect_commit_success().outcome.expect_success();
+    call_result[1].expect_return_value(&true);
+}
+
+#[test]
+fn test_submodule_rejects_message() {
+    let mut suite = common::setup();
+    let pauser_badge =
+        suite
+            .ledger
+            .create_fungible_resource(dec!(1), DIVISIBILITY_NONE, suite.account.address);
+    let result = suite.instantiate_blueprint("PausableIsm", manifest_args!(pauser_badge));
+    result.0.expect_commit_success();
+    let submodule = result.1.unwrap();
+    suite
+        .call_method_with_badge(submodule, "pause", pauser_badge, manifest_args!())
+        .expect_commit_success();
+
+    let (ism, _, _) = create_optimistic_ism(&mut suite, submodule);
+
+    let receipt = pre_verify(&mut suite, ism, 0);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("OptimisticIsm: submodule verification failed"));
+}
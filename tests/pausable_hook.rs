@@ -0,0 +1,118 @@
+use crate::common::Suite;
+use hyperlane_radix::types::Bytes32;
+use scrypto_test::prelude::*;
+
+mod common;
+
+fn create_pausable_hook(suite: &mut Suite) -> (ComponentAddress, ResourceAddress, ResourceAddress) {
+    let pauser_badge =
+        suite
+            .ledger
+            .create_fungible_resource(dec!(1), DIVISIBILITY_NONE, suite.account.address);
+
+    let result = suite.instantiate_blueprint("PausableHook", manifest_args!(pauser_badge));
+    result.0.expect_commit_success();
+    (result.1.unwrap(), result.2.unwrap(), pauser_badge)
+}
+
+fn create_mailbox(suite: &mut Suite, hook: ComponentAddress) -> ComponentAddress {
+    let result = suite.instantiate_blueprint("Mailbox", manifest_args!(100u32));
+    result.0.expect_commit_success();
+    let (mailbox, owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_required_hook",
+            owner_badge,
+            manifest_args!(hook),
+        )
+        .expect_commit_success();
+
+    mailbox
+}
+
+fn dispatch_message(suite: &mut Suite, mailbox: ComponentAddress) -> TransactionReceipt {
+    let claimed_account_address = suite.account.address;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            mailbox,
+            "dispatch",
+            manifest_args!(
+                1337u32,
+                Bytes32::zero(),
+                Vec::<u8>::new(),
+                None::<ComponentAddress>,
+                None::<(Decimal, Option<Vec<u8>>)>,
+                Vec::<ManifestBucket>::new(),
+                ManifestValue::enum_variant(
+                    1u8,
+                    vec![ManifestValue::Custom {
+                        value: ManifestCustomValue::Address(ManifestAddress::Static(
+                            *claimed_account_address.as_node_id()
+                        )),
+                    }]
+                )
+            ),
+        )
+        .deposit_batch(suite.account.address, ManifestExpression::EntireWorktop)
+        .build();
+
+    suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    )
+}
+
+#[test]
+fn test_paused_hook_rejects_dispatch() {
+    let mut suite = common::setup();
+    let (hook, owner_badge, pauser_badge) = create_pausable_hook(&mut suite);
+    let mailbox = create_mailbox(&mut suite, hook);
+
+    dispatch_message(&mut suite, mailbox).expect_commit_success();
+
+    suite
+        .call_method_with_badge(hook, "pause", pauser_badge, manifest_args!())
+        .expect_commit_success();
+    let paused: bool = suite.call_method_success(hook, "paused", manifest_args!());
+    assert!(paused);
+
+    let receipt = dispatch_message(&mut suite, mailbox);
+    assert!(format!("{:?}", receipt.expect_commit_failure()).contains("PausableHook: paused"));
+
+    suite
+        .call_method_with_badge(hook, "unpause", owner_badge, manifest_args!())
+        .expect_commit_success();
+    dispatch_message(&mut suite, mailbox).expect_commit_success();
+}
+
+#[test]
+fn test_pause_permissions() {
+    let mut suite = common::setup();
+    let (hook, owner_badge, pauser_badge) = create_pausable_hook(&mut suite);
+
+    // Anyone else can not pause
+    let receipt = suite.call_method(hook, "pause", manifest_args!());
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("SystemModuleError(AuthError(Unauthorized(Unauthorized"));
+
+    // The owner can pause as well
+    suite
+        .call_method_with_badge(hook, "pause", owner_badge, manifest_args!())
+        .expect_commit_success();
+
+    // The pauser can not unpause
+    let receipt = suite.call_method_with_badge(hook, "unpause", pauser_badge, manifest_args!());
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("SystemModuleError(AuthError(Unauthorized(Unauthorized"));
+
+    suite
+        .call_method_with_badge(hook, "unpause", owner_badge, manifest_args!())
+        .expect_commit_success();
+    let receipt = suite.call_method_with_badge(hook, "unpause", owner_badge, manifest_args!());
+    assert!(format!("{:?}", receipt.expect_commit_failure()).contains("PausableHook: not paused"));
+}
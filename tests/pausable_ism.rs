@@ -0,0 +1,67 @@
+use crate::common::Suite;
+use scrypto_test::prelude::*;
+
+mod common;
+
+fn create_pausable_ism(suite: &mut Suite) -> (ComponentAddress, ResourceAddress, ResourceAddress) {
+    let pauser_badge =
+        suite
+            .ledger
+            .create_fungible_resource(dec!(1), DIVISIBILITY_NONE, suite.account.address);
+
+    let result = suite.instantiate_blueprint("PausableIsm", manifest_args!(pauser_badge));
+    result.0.expect_commit_success();
+    (result.1.unwrap(), result.2.unwrap(), pauser_badge)
+}
+
+#[test]
+fn test_paused_ism_rejects_messages() {
+    let mut suite = common::setup();
+    let (ism, owner_badge, pauser_badge) = create_pausable_ism(&mut suite);
+
+    let verified: bool = suite.call_method_success(
+        ism,
+        "verify",
+        manifest_args!(Vec::<u8>::new(), Vec::<u8>::new()),
+    );
+    assert!(verified);
+
+    suite
+        .call_method_with_badge(ism, "pause", pauser_badge, manifest_args!())
+        .expect_commit_success();
+    let verified: bool = suite.call_method_success(
+        ism,
+        "verify",
+        manifest_args!(Vec::<u8>::new(), Vec::<u8>::new()),
+    );
+    assert!(!verified);
+
+    // The pauser can not unpause
+    let receipt = suite.call_method_with_badge(ism, "unpause", pauser_badge, manifest_args!());
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("SystemModuleError(AuthError(Unauthorized(Unauthorized"));
+
+    suite
+        .call_method_with_badge(ism, "unpause", owner_badge, manifest_args!())
+        .expect_commit_success();
+    let verified: bool = suite.call_method_success(
+        ism,
+        "verify",
+        manifest_args!(Vec::<u8>::new(), Vec::<u8>::new()),
+    );
+    assert!(verified);
+}
+
+#[test]
+fn test_pause_twice() {
+    let mut suite = common::setup();
+    let (ism, owner_badge, _) = create_pausable_ism(&mut suite);
+
+    suite
+        .call_method_with_badge(ism, "pause", owner_badge, manifest_args!())
+        .expect_commit_success();
+    let receipt = suite.call_method_with_badge(ism, "pause", owner_badge, manifest_args!());
+    assert!(
+        format!("{:?}", receipt.expect_commit_failure()).contains("PausableIsm: already paused")
+    );
+}
@@ -0,0 +1,196 @@
+use crate::common::Suite;
+use hyperlane_radix::types::Bytes32;
+use scrypto_test::prelude::*;
+
+mod common;
+
+fn create_protocol_fee_hook(
+    suite: &mut Suite,
+    max_protocol_fee: Decimal,
+    protocol_fee: Decimal,
+    beneficiary: ComponentAddress,
+) -> (
+    TransactionReceipt,
+    Option<ComponentAddress>,
+    Option<ResourceAddress>,
+) {
+    suite.instantiate_blueprint(
+        "ProtocolFeeHook",
+        manifest_args!(XRD, max_protocol_fee, protocol_fee, beneficiary),
+    )
+}
+
+fn post_dispatch(
+    suite: &mut Suite,
+    hook: ComponentAddress,
+    payment: Decimal,
+) -> TransactionReceipt {
+    let metadata = Some((Decimal::one(), Option::<Vec<u8>>::None));
+    let message = (
+        3u8,
+        0u32,
+        0u32,
+        Bytes32::zero(),
+        1337u32,
+        Bytes32::zero(),
+        Vec::<u8>::new(),
+    );
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(suite.account.address, XRD, payment)
+        .take_from_worktop(XRD, payment, "payment")
+        .call_method_with_name_lookup(hook, "post_dispatch", |lookup| {
+            manifest_args!(metadata, message, vec![lookup.bucket("payment")])
+        })
+        .deposit_batch(suite.account.address, ManifestExpression::EntireWorktop)
+        .build();
+
+    suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    )
+}
+
+#[test]
+fn test_create_with_fee_above_max() {
+    let mut suite = common::setup();
+    let beneficiary = suite.dummy_accounts[0].address;
+    let (receipt, _, _) = create_protocol_fee_hook(&mut suite, dec!(5), dec!(6), beneficiary);
+
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("ProtocolFeeHook: protocol fee 6 exceeds max protocol fee 5"));
+}
+
+#[test]
+fn test_post_dispatch_charges_fee() {
+    let mut suite = common::setup();
+    let beneficiary = suite.dummy_accounts[0].address;
+    let (receipt, hook, _) = create_protocol_fee_hook(&mut suite, dec!(5), dec!(2), beneficiary);
+    receipt.expect_commit_success();
+    let hook = hook.unwrap();
+
+    let metadata = Some((Decimal::one(), Option::<Vec<u8>>::None));
+    let quote: IndexMap<ResourceAddress, Decimal> = suite.call_method_success(
+        hook,
+        "quote_dispatch",
+        manifest_args!(
+            metadata,
+            (
+                3u8,
+                0u32,
+                0u32,
+                Bytes32::zero(),
+                1337u32,
+                Bytes32::zero(),
+                Vec::<u8>::new()
+            )
+        ),
+    );
+    let expected: indexmap::IndexMap<ResourceAddress, Decimal> =
+        IndexMap::from_iter(vec![(XRD, dec!(2))]);
+    assert_eq!(quote, expected);
+
+    // the leftover 8 XRD are returned
+    let balance_before = suite
+        .ledger
+        .get_component_balance(suite.account.address, XRD);
+    post_dispatch(&mut suite, hook, dec!(10)).expect_commit_success();
+    assert_eq!(suite.ledger.get_component_balance(hook, XRD), dec!(2));
+    assert_eq!(
+        suite
+            .ledger
+            .get_component_balance(suite.account.address, XRD),
+        balance_before - dec!(2)
+    );
+}
+
+#[test]
+fn test_post_dispatch_insufficient_payment() {
+    let mut suite = common::setup();
+    let beneficiary = suite.dummy_accounts[0].address;
+    let (receipt, hook, _) = create_protocol_fee_hook(&mut suite, dec!(5), dec!(2), beneficiary);
+    receipt.expect_commit_success();
+
+    let receipt = post_dispatch(&mut suite, hook.unwrap(), dec!(1));
+
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("ProtocolFeeHook: insufficient payment for protocol fee. fee: 2"));
+}
+
+#[test]
+fn test_set_protocol_fee() {
+    let mut suite = common::setup();
+    let beneficiary = suite.dummy_accounts[0].address;
+    let (receipt, hook, owner_badge) =
+        create_protocol_fee_hook(&mut suite, dec!(5), dec!(2), beneficiary);
+    receipt.expect_commit_success();
+    let (hook, owner_badge) = (hook.unwrap(), owner_badge.unwrap());
+
+    // Non-owner can not change the fee
+    let receipt = suite.call_method(hook, "set_protocol_fee", manifest_args!(dec!(1)));
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("SystemModuleError(AuthError(Unauthorized(Unauthorized"));
+
+    // The fee is bounded by the max fee
+    let receipt = suite.call_method_with_badge(
+        hook,
+        "set_protocol_fee",
+        owner_badge,
+        manifest_args!(dec!(6)),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("ProtocolFeeHook: protocol fee 6 exceeds max protocol fee 5"));
+
+    suite
+        .call_method_with_badge(
+            hook,
+            "set_protocol_fee",
+            owner_badge,
+            manifest_args!(dec!(5)),
+        )
+        .expect_commit_success();
+    let fee: Decimal = suite.call_method_success(hook, "protocol_fee", manifest_args!());
+    assert_eq!(fee, dec!(5));
+}
+
+#[test]
+fn test_collect_fees() {
+    let mut suite = common::setup();
+    let beneficiary = suite.dummy_accounts[0].address;
+    let new_beneficiary = suite.dummy_accounts[1].address;
+    let (receipt, hook, owner_badge) =
+        create_protocol_fee_hook(&mut suite, dec!(5), dec!(2), beneficiary);
+    receipt.expect_commit_success();
+    let (hook, owner_badge) = (hook.unwrap(), owner_badge.unwrap());
+
+    post_dispatch(&mut suite, hook, dec!(2)).expect_commit_success();
+    post_dispatch(&mut suite, hook, dec!(2)).expect_commit_success();
+
+    // Non-owner can not collect the fees
+    let receipt = suite.call_method(hook, "collect_fees", manifest_args!());
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("SystemModuleError(AuthError(Unauthorized(Unauthorized"));
+
+    suite
+        .call_method_with_badge(
+            hook,
+            "set_beneficiary",
+            owner_badge,
+            manifest_args!(new_beneficiary),
+        )
+        .expect_commit_success();
+
+    let balance_before = suite.ledger.get_component_balance(new_beneficiary, XRD);
+    suite
+        .call_method_with_badge(hook, "collect_fees", owner_badge, manifest_args!())
+        .expect_commit_success();
+
+    assert_eq!(suite.ledger.get_component_balance(hook, XRD), dec!(0));
+    assert_eq!(
+        suite.ledger.get_component_balance(new_beneficiary, XRD),
+        balance_before + dec!(4)
+    );
+}
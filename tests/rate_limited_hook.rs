@@ -0,0 +1,264 @@
+use crate::common::Suite;
+use hyperlane_radix::types::{warp_payload::WarpPayload, Bytes32};
+use scrypto_test::prelude::*;
+
+mod common;
+
+fn setup_rate_limited_mailbox(
+    suite: &mut Suite,
+    max_amount: Decimal,
+) -> (ComponentAddress, ComponentAddress, ResourceAddress) {
+    let result = suite.instantiate_blueprint("Mailbox", manifest_args!(100u32));
+    result.0.expect_commit_success();
+    let (mailbox, mailbox_owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    let result = suite.instantiate_blueprint("RateLimitedHook", manifest_args!(mailbox));
+    result.0.expect_commit_success();
+    let (hook, owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    let sender: Bytes32 = suite.account.address.into();
+    suite
+        .call_method_with_badge(
+            hook,
+            "set_sender_limit",
+            owner_badge,
+            manifest_args!(sender, max_amount, 18u32),
+        )
+        .expect_commit_success();
+
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_required_hook",
+            mailbox_owner_badge,
+            manifest_args!(hook),
+        )
+        .expect_commit_success();
+
+    (mailbox, hook, owner_badge)
+}
+
+fn dispatch_transfer(
+    suite: &mut Suite,
+    mailbox: ComponentAddress,
+    amount: Decimal,
+) -> TransactionReceipt {
+    let body: Vec<u8> = WarpPayload::try_new_with_divisibility(Bytes32::zero(), amount, 18)
+        .unwrap()
+        .into();
+    let claimed_account_address = suite.account.address;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            mailbox,
+            "dispatch",
+            manifest_args!(
+                1337u32,
+                Bytes32::zero(),
+                body,
+                None::<ComponentAddress>,
+                None::<(Decimal, Option<Vec<u8>>)>,
+                Vec::<ManifestBucket>::new(),
+                ManifestValue::enum_variant(
+                    1u8,
+                    vec![ManifestValue::Custom {
+                        value: ManifestCustomValue::Address(ManifestAddress::Static(
+                            *claimed_account_address.as_node_id()
+                        )),
+                    }]
+                )
+            ),
+        )
+        .deposit_batch(suite.account.address, ManifestExpression::EntireWorktop)
+        .build();
+
+    suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    )
+}
+
+#[test]
+fn test_rate_limit_exceeded() {
+    let mut suite = common::setup();
+    let (mailbox, _, _) = setup_rate_limited_mailbox(&mut suite, dec!(1000));
+
+    dispatch_transfer(&mut suite, mailbox, dec!(600)).expect_commit_success();
+
+    let receipt = dispatch_transfer(&mut suite, mailbox, dec!(500));
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("RateLimitedHook: rate limit exceeded. amount: 500, available: 400"));
+
+    dispatch_transfer(&mut suite, mailbox, dec!(400)).expect_commit_success();
+}
+
+#[test]
+fn test_rate_limit_refills_over_time() {
+    let mut suite = common::setup();
+    let (mailbox, hook, _) = setup_rate_limited_mailbox(&mut suite, dec!(1000));
+    let sender: Bytes32 = suite.account.address.into();
+
+    suite.ledger.advance_to_round_at_timestamp(Round::of(1), 0);
+    dispatch_transfer(&mut suite, mailbox, dec!(1000)).expect_commit_success();
+
+    let level: Option<Decimal> =
+        suite.call_method_success(hook, "current_level", manifest_args!(sender));
+    assert_eq!(level, Some(dec!(0)));
+
+    // half of the window has passed
+    suite
+        .ledger
+        .advance_to_round_at_timestamp(Round::of(2), 43_200_000);
+    let level: Option<Decimal> =
+        suite.call_method_success(hook, "current_level", manifest_args!(sender));
+    assert_eq!(level, Some(dec!(500)));
+
+    // the level is capped at the max amount
+    suite
+        .ledger
+        .advance_to_round_at_timestamp(Round::of(3), 3 * 86_400_000);
+    let level: Option<Decimal> =
+        suite.call_method_success(hook, "current_level", manifest_args!(sender));
+    assert_eq!(level, Some(dec!(1000)));
+}
+
+#[test]
+fn test_set_sender_limit() {
+    let mut suite = common::setup();
+    let (mailbox, hook, owner_badge) = setup_rate_limited_mailbox(&mut suite, dec!(1000));
+    let sender: Bytes32 = suite.account.address.into();
+
+    let receipt = suite.call_method(
+        hook,
+        "set_sender_limit",
+        manifest_args!(sender, dec!(10), 18u32),
+    );
+    receipt.expect_auth_failure();
+
+    let receipt = suite.call_method_with_badge(
+        hook,
+        "set_sender_limit",
+        owner_badge,
+        manifest_args!(sender, dec!(0), 18u32),
+    );
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("RateLimitedHook: max amount must be positive"));
+
+    suite
+        .call_method_with_badge(
+            hook,
+            "set_sender_limit",
+            owner_badge,
+            manifest_args!(sender, dec!(10), 18u32),
+        )
+        .expect_commit_success();
+
+    let receipt = dispatch_transfer(&mut suite, mailbox, dec!(11));
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("RateLimitedHook: rate limit exceeded"));
+}
+
+#[test]
+fn test_divisibility_per_sender() {
+    let mut suite = common::setup();
+    let (mailbox, hook, owner_badge) = setup_rate_limited_mailbox(&mut suite, dec!(1000));
+    let sender: Bytes32 = suite.account.address.into();
+
+    // the payload amount of 1 with 18 decimals is 10^12 with 6 decimals
+    suite
+        .call_method_with_badge(
+            hook,
+            "set_sender_limit",
+            owner_badge,
+            manifest_args!(sender, dec!(1000), 6u32),
+        )
+        .expect_commit_success();
+
+    let receipt = dispatch_transfer(&mut suite, mailbox, dec!(1));
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("RateLimitedHook: rate limit exceeded"));
+}
+
+#[test]
+fn test_remove_sender_limit() {
+    let mut suite = common::setup();
+    let (mailbox, hook, owner_badge) = setup_rate_limited_mailbox(&mut suite, dec!(1000));
+    let sender: Bytes32 = suite.account.address.into();
+
+    dispatch_transfer(&mut suite, mailbox, dec!(1000)).expect_commit_success();
+
+    suite
+        .call_method_with_badge(
+            hook,
+            "remove_sender_limit",
+            owner_badge,
+            manifest_args!(sender),
+        )
+        .expect_commit_success();
+
+    let level: Option<Decimal> =
+        suite.call_method_success(hook, "current_level", manifest_args!(sender));
+    assert_eq!(level, None);
+
+    // the sender is not rate limited anymore
+    dispatch_transfer(&mut suite, mailbox, dec!(5000)).expect_commit_success();
+}
+
+fn dispatch_body(
+    suite: &mut Suite,
+    mailbox: ComponentAddress,
+    body: Vec<u8>,
+) -> TransactionReceipt {
+    let claimed_account_address = suite.account.address;
+    suite.call_method(
+        mailbox,
+        "dispatch",
+        manifest_args!(
+            1337u32,
+            Bytes32::zero(),
+            body,
+            None::<ComponentAddress>,
+            None::<(Decimal, Option<Vec<u8>>)>,
+            Vec::<ManifestBucket>::new(),
+            ManifestValue::enum_variant(
+                1u8,
+                vec![ManifestValue::Custom {
+                    value: ManifestCustomValue::Address(ManifestAddress::Static(
+                        *claimed_account_address.as_node_id()
+                    )),
+                }]
+            )
+        ),
+    )
+}
+
+#[test]
+fn test_invalid_payload() {
+    let mut suite = common::setup();
+    let (mailbox, _, _) = setup_rate_limited_mailbox(&mut suite, dec!(1000));
+
+    let receipt = dispatch_body(&mut suite, mailbox, vec![1u8, 2u8, 3u8]);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("RateLimitedHook: failed to decode warp payload"));
+}
+
+#[test]
+fn test_unlimited_sender_passes_through() {
+    let mut suite = common::setup();
+    let (mailbox, hook, owner_badge) = setup_rate_limited_mailbox(&mut suite, dec!(1000));
+    let sender: Bytes32 = suite.account.address.into();
+
+    suite
+        .call_method_with_badge(
+            hook,
+            "remove_sender_limit",
+            owner_badge,
+            manifest_args!(sender),
+        )
+        .expect_commit_success();
+
+    // messages of senders without a limit are not decoded
+    dispatch_body(&mut suite, mailbox, vec![1u8, 2u8, 3u8]).expect_commit_success();
+}
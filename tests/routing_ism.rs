@@ -1,5 +1,5 @@
 use crate::common::Suite;
-use hyperlane_radix::types::HyperlaneMessage;
+use hyperlane_radix::types::{Bytes32, HyperlaneMessage};
 use scrypto_test::prelude::*;
 
 mod common;
@@ -218,3 +218,318 @@ fn test_remove_route() {
     assert!(format!("{:?}", failure_receipt.expect_commit_failure())
         .contains("RoutingIsm: no ISM for route 1"));
 }
+
+#[test]
+fn test_set_routes_and_enumerate() {
+    // Arrange
+    let mut suite = common::setup();
+    let ism_a = create_noop_ism(&mut suite);
+    let ism_b = create_noop_ism(&mut suite);
+    let receipt = create_routing_ism(&mut suite, vec![(5, ism_a)]);
+    let component_address = receipt.expect_commit_success().new_component_addresses()[0];
+    let owner_badge = receipt.expect_commit_success().new_resource_addresses()[0];
+
+    // Act
+    let routes: Vec<(u32, ComponentAddress)> = vec![(7, ism_b), (1, ism_a), (5, ism_b)];
+    let receipt = suite.call_method_with_badge(
+        component_address,
+        "set_routes",
+        owner_badge,
+        manifest_args!(routes),
+    );
+
+    // Assert
+    let route_set_events = receipt
+        .expect_commit_success()
+        .application_events
+        .iter()
+        .filter(|event| event.0 .1 == "RouteSet")
+        .count();
+    assert_eq!(route_set_events, 3);
+
+    let domains: Vec<u32> =
+        suite.call_method_success(component_address, "domains", manifest_args!());
+    assert_eq!(domains, vec![1, 5, 7]);
+
+    let routes: Vec<(u32, ComponentAddress)> =
+        suite.call_method_success(component_address, "routes", manifest_args!());
+    assert_eq!(routes, vec![(1, ism_a), (5, ism_b), (7, ism_b)]);
+
+    let receipt = suite.call_method_with_badge(
+        component_address,
+        "remove_route",
+        owner_badge,
+        manifest_args!(5u32),
+    );
+    let route_removed: hyperlane_radix::contracts::isms::routing_ism::RouteRemoved = receipt
+        .expect_commit_success()
+        .application_events
+        .iter()
+        .find(|event| event.0 .1 == "RouteRemoved")
+        .map(|event| scrypto_decode(&event.1).expect("Failed to decode event"))
+        .unwrap();
+    assert_eq!(route_removed.domain, 5);
+
+    let domains: Vec<u32> =
+        suite.call_method_success(component_address, "domains", manifest_args!());
+    assert_eq!(domains, vec![1, 7]);
+}
+
+#[test]
+fn test_non_owner_can_not_set_routes() {
+    let mut suite = common::setup();
+    let ism = create_noop_ism(&mut suite);
+    let receipt = create_routing_ism(&mut suite, vec![]);
+    let component_address = receipt.expect_commit_success().new_component_addresses()[0];
+
+    let routes: Vec<(u32, ComponentAddress)> = vec![(1, ism)];
+    let receipt = suite.call_method(component_address, "set_routes", manifest_args!(routes));
+    receipt.expect_auth_failure();
+}
+
+fn raw_message(origin: u32, sender: Bytes32) -> Vec<u8> {
+    raw_message_to(origin, sender, Default::default())
+}
+
+fn raw_message_to(origin: u32, sender: Bytes32, recipient: Bytes32) -> Vec<u8> {
+    HyperlaneMessage {
+        version: 3,
+        nonce: 0,
+        origin,
+        sender,
+        destination: 0,
+        recipient,
+        body: vec![],
+    }
+    .into()
+}
+
+#[test]
+fn test_sender_route_falls_back_to_origin_route() {
+    // Arrange
+    let mut suite = common::setup();
+    let origin_ism = create_noop_ism(&mut suite);
+    let sender_ism = create_noop_ism(&mut suite);
+    let receipt = create_routing_ism(&mut suite, vec![(1, origin_ism)]);
+    let component_address = receipt.expect_commit_success().new_component_addresses()[0];
+    let owner_badge = receipt.expect_commit_success().new_resource_addresses()[0];
+
+    let sender: Bytes32 = [1u8; 32].into();
+    let other_sender: Bytes32 = [2u8; 32].into();
+
+    // Act
+    suite
+        .call_method_with_badge(
+            component_address,
+            "set_sender_route",
+            owner_badge,
+            manifest_args!(1u32, sender, sender_ism),
+        )
+        .expect_commit_success();
+    suite
+        .call_method_with_badge(
+            component_address,
+            "set_sender_route",
+            owner_badge,
+            manifest_args!(2u32, sender, sender_ism),
+        )
+        .expect_commit_success();
+
+    // Assert
+    let sender_routes: Vec<(u32, Bytes32, ComponentAddress)> =
+        suite.call_method_success(component_address, "sender_routes", manifest_args!());
+    assert_eq!(
+        sender_routes,
+        vec![(1, sender, sender_ism), (2, sender, sender_ism)]
+    );
+
+    let ism: ComponentAddress = suite.call_method_success(
+        component_address,
+        "route",
+        manifest_args!(raw_message(1, sender)),
+    );
+    assert_eq!(ism, sender_ism);
+
+    let ism: ComponentAddress = suite.call_method_success(
+        component_address,
+        "route",
+        manifest_args!(raw_message(1, other_sender)),
+    );
+    assert_eq!(ism, origin_ism);
+
+    let ism: ComponentAddress = suite.call_method_success(
+        component_address,
+        "route",
+        manifest_args!(raw_message(2, sender)),
+    );
+    assert_eq!(ism, sender_ism);
+
+    let receipt = suite.call_method(
+        component_address,
+        "route",
+        manifest_args!(raw_message(2, other_sender)),
+    );
+    assert!(
+        format!("{:?}", receipt.expect_commit_failure()).contains("RoutingIsm: no ISM for route 2")
+    );
+
+    suite
+        .call_method_with_badge(
+            component_address,
+            "remove_sender_route",
+            owner_badge,
+            manifest_args!(1u32, sender),
+        )
+        .expect_commit_success();
+
+    let ism: ComponentAddress = suite.call_method_success(
+        component_address,
+        "route",
+        manifest_args!(raw_message(1, sender)),
+    );
+    assert_eq!(ism, origin_ism);
+
+    let sender_route: Option<ComponentAddress> = suite.call_method_success(
+        component_address,
+        "sender_route",
+        manifest_args!(1u32, sender),
+    );
+    assert_eq!(sender_route, None);
+
+    let sender_routes: Vec<(u32, Bytes32, ComponentAddress)> =
+        suite.call_method_success(component_address, "sender_routes", manifest_args!());
+    assert_eq!(sender_routes, vec![(2, sender, sender_ism)]);
+}
+
+#[test]
+fn test_non_owner_can_not_set_sender_route() {
+    let mut suite = common::setup();
+    let ism = create_noop_ism(&mut suite);
+    let receipt = create_routing_ism(&mut suite, vec![]);
+    let component_address = receipt.expect_commit_success().new_component_addresses()[0];
+
+    let sender: Bytes32 = [1u8; 32].into();
+    let receipt = suite.call_method(
+        component_address,
+        "set_sender_route",
+        manifest_args!(1u32, sender, ism),
+    );
+    receipt.expect_auth_failure();
+}
+
+#[test]
+fn test_recipient_route_falls_back_to_sender_route() {
+    // Arrange
+    let mut suite = common::setup();
+    let origin_ism = create_noop_ism(&mut suite);
+    let sender_ism = create_noop_ism(&mut suite);
+    let recipient_ism = create_noop_ism(&mut suite);
+    let receipt = create_routing_ism(&mut suite, vec![(1, origin_ism)]);
+    let component_address = receipt.expect_commit_success().new_component_addresses()[0];
+    let owner_badge = receipt.expect_commit_success().new_resource_addresses()[0];
+
+    let sender: Bytes32 = [1u8; 32].into();
+    let other_sender: Bytes32 = [2u8; 32].into();
+    let recipient: Bytes32 = [3u8; 32].into();
+    let other_recipient: Bytes32 = [4u8; 32].into();
+
+    // Act
+    suite
+        .call_method_with_badge(
+            component_address,
+            "set_sender_route",
+            owner_badge,
+            manifest_args!(1u32, sender, sender_ism),
+        )
+        .expect_commit_success();
+    suite
+        .call_method_with_badge(
+            component_address,
+            "set_recipient_route",
+            owner_badge,
+            manifest_args!(1u32, sender, recipient, recipient_ism),
+        )
+        .expect_commit_success();
+    suite
+        .call_method_with_badge(
+            component_address,
+            "set_recipient_route",
+            owner_badge,
+            manifest_args!(1u32, other_sender, recipient, recipient_ism),
+        )
+        .expect_commit_success();
+
+    // Assert
+    let recipient_routes: Vec<(u32, Bytes32, Bytes32, ComponentAddress)> =
+        suite.call_method_success(component_address, "recipient_routes", manifest_args!());
+    assert_eq!(
+        recipient_routes,
+        vec![
+            (1, sender, recipient, recipient_ism),
+            (1, other_sender, recipient, recipient_ism)
+        ]
+    );
+
+    // Local applications behind the same routing ISM are told apart by the recipient
+    let cases = [
+        (sender, recipient, recipient_ism),
+        (sender, other_recipient, sender_ism),
+        (other_sender, recipient, recipient_ism),
+        (other_sender, other_recipient, origin_ism),
+    ];
+    for (message_sender, message_recipient, expected) in cases {
+        let ism: ComponentAddress = suite.call_method_success(
+            component_address,
+            "route",
+            manifest_args!(raw_message_to(1, message_sender, message_recipient)),
+        );
+        assert_eq!(ism, expected);
+    }
+
+    suite
+        .call_method_with_badge(
+            component_address,
+            "remove_recipient_route",
+            owner_badge,
+            manifest_args!(1u32, sender, recipient),
+        )
+        .expect_commit_success();
+
+    let ism: ComponentAddress = suite.call_method_success(
+        component_address,
+        "route",
+        manifest_args!(raw_message_to(1, sender, recipient)),
+    );
+    assert_eq!(ism, sender_ism);
+
+    let recipient_route: Option<ComponentAddress> = suite.call_method_success(
+        component_address,
+        "recipient_route",
+        manifest_args!(1u32, sender, recipient),
+    );
+    assert_eq!(recipient_route, None);
+
+    let recipient_routes: Vec<(u32, Bytes32, Bytes32, ComponentAddress)> =
+        suite.call_method_success(component_address, "recipient_routes", manifest_args!());
+    assert_eq!(
+        recipient_routes,
+        vec![(1, other_sender, recipient, recipient_ism)]
+    );
+}
+
+#[test]
+fn test_non_owner_can_not_set_recipient_route() {
+    let mut suite = common::setup();
+    let ism = create_noop_ism(&mut suite);
+    let receipt = create_routing_ism(&mut suite, vec![]);
+    let component_address = receipt.expect_commit_success().new_component_addresses()[0];
+
+    let sender: Bytes32 = [1u8; 32].into();
+    let recipient: Bytes32 = [3u8; 32].into();
+    let receipt = suite.call_method(
+        component_address,
+        "set_recipient_route",
+        manifest_args!(1u32, sender, recipient, ism),
+    );
+    receipt.expect_auth_failure();
+}
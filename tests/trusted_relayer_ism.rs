@@ -0,0 +1,196 @@
+use crate::common::{hex_str_to_bytes32, Suite};
+use hyperlane_radix::types::{Bytes32, HyperlaneMessage};
+use scrypto_test::prelude::*;
+
+mod common;
+
+// Transfer of 50 tokens from domain 1337 to a synthetic token on domain 1000
+const PAYLOAD: &str = "0300000000000005390000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496000003e80000c07341fadfb99d506736cf979374b560851b181d9e83e225d5437ac270e80000c1f7abd48c518b8ebdc6a35abfbe78583725a97eabdc99224571e0d11d42000000000000000000000000000000000000000000000002b5e3af16b1880000";
+
+/// Creates a mailbox with a trusted relayer ISM as default ISM and a synthetic token that
+/// receives the transfer in `PAYLOAD`
+fn setup_trusted_relayer_mailbox(
+    suite: &mut Suite,
+    relayer: AccessRule,
+) -> (ComponentAddress, ComponentAddress, ComponentAddress) {
+    let result = suite.instantiate_blueprint("Mailbox", manifest_args!(1000u32));
+    result.0.expect_commit_success();
+    let (mailbox, mailbox_owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    let result = suite.instantiate_blueprint("TrustedRelayerIsm", manifest_args!(relayer));
+    result.0.expect_commit_success();
+    let ism = result.1.unwrap();
+
+    suite
+        .call_method_with_badge(
+            mailbox,
+            "set_default_ism",
+            mailbox_owner_badge,
+            manifest_args!(ism),
+        )
+        .expect_commit_success();
+
+    let result = suite.instantiate_blueprint(
+        "HypToken",
+        manifest_args!(
+            ManifestValue::enum_variant(
+                1u8,
+                vec![
+                    ManifestValue::String {
+                        value: "Eth".to_string()
+                    },
+                    ManifestValue::String {
+                        value: "Ether".to_string()
+                    },
+                    ManifestValue::String {
+                        value: "Native ETH from Ethereum".to_string()
+                    },
+                    ManifestValue::U8 { value: 18 },
+                ]
+            ),
+            mailbox
+        ),
+    );
+    result.0.expect_commit_success();
+    let (token, token_owner_badge) = (result.1.unwrap(), result.2.unwrap());
+
+    let remote_router: Bytes32 =
+        hex_str_to_bytes32("0000000000000000000000007fa9385be102ac3eac297483dd6233d62b3e1496");
+    suite
+        .call_method_with_badge(
+            token,
+            "enroll_remote_router",
+            token_owner_badge,
+            manifest_args!(1337u32, remote_router, dec!(12)),
+        )
+        .expect_commit_success();
+
+    (mailbox, ism, token)
+}
+
+fn process(
+    suite: &mut Suite,
+    mailbox: ComponentAddress,
+    token: ComponentAddress,
+) -> TransactionReceipt {
+    let visible_components = vec![suite.account.address, token];
+    suite.call_method(
+        mailbox,
+        "process",
+        manifest_args!(
+            Vec::<u8>::new(),
+            hex::decode(PAYLOAD).unwrap(),
+            visible_components
+        ),
+    )
+}
+
+/// Approves and processes the message in `PAYLOAD` in a single transaction
+fn approve_and_process(
+    suite: &mut Suite,
+    mailbox: ComponentAddress,
+    ism: ComponentAddress,
+    token: ComponentAddress,
+    relayer_badge: Option<ResourceAddress>,
+) -> TransactionReceipt {
+    let message = hex::decode(PAYLOAD).unwrap();
+    let visible_components = vec![suite.account.address, token];
+
+    let mut manifest = ManifestBuilder::new().lock_fee_from_faucet();
+    if let Some(relayer_badge) = relayer_badge {
+        manifest = manifest.create_proof_from_account_of_amount(
+            suite.account.address,
+            relayer_badge,
+            dec!(1),
+        );
+    }
+    let manifest = manifest
+        .call_method(ism, "approve", manifest_args!(message.clone()))
+        .call_method(
+            mailbox,
+            "process",
+            manifest_args!(Vec::<u8>::new(), message, visible_components),
+        )
+        .build();
+
+    suite.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &suite.account.public_key,
+        )],
+    )
+}
+
+#[test]
+fn test_relayer_badge() {
+    let mut suite = common::setup();
+    let relayer_badge =
+        suite
+            .ledger
+            .create_fungible_resource(dec!(1), DIVISIBILITY_NONE, suite.account.address);
+    let (mailbox, ism, token) =
+        setup_trusted_relayer_mailbox(&mut suite, rule!(require(relayer_badge)));
+
+    // Messages that have not been approved are rejected
+    let receipt = process(&mut suite, mailbox, token);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Mailbox: ISM verification failed"));
+
+    // Approving without the relayer badge fails
+    let receipt = approve_and_process(&mut suite, mailbox, ism, token, None);
+    assert!(format!("{:?}", receipt.expect_commit_failure()).contains("AssertAccessRuleFailed"));
+
+    approve_and_process(&mut suite, mailbox, ism, token, Some(relayer_badge))
+        .expect_commit_success();
+}
+
+#[test]
+fn test_relayer_account() {
+    let mut suite = common::setup();
+    let relayer = NonFungibleGlobalId::from_public_key(&suite.account.public_key);
+    let (mailbox, ism, token) = setup_trusted_relayer_mailbox(&mut suite, rule!(require(relayer)));
+
+    approve_and_process(&mut suite, mailbox, ism, token, None).expect_commit_success();
+
+    // A different account is not a trusted relayer
+    let other = NonFungibleGlobalId::from_public_key(&suite.dummy_accounts[0].public_key);
+    let (mailbox, ism, token) = setup_trusted_relayer_mailbox(&mut suite, rule!(require(other)));
+
+    let receipt = approve_and_process(&mut suite, mailbox, ism, token, None);
+    assert!(format!("{:?}", receipt.expect_commit_failure()).contains("AssertAccessRuleFailed"));
+}
+
+#[test]
+fn test_approval_only_valid_in_its_transaction() {
+    let mut suite = common::setup();
+    let relayer = NonFungibleGlobalId::from_public_key(&suite.account.public_key);
+    let (mailbox, ism, token) = setup_trusted_relayer_mailbox(&mut suite, rule!(require(relayer)));
+    let message = hex::decode(PAYLOAD).unwrap();
+    let message_id = HyperlaneMessage::from(message.clone()).id();
+
+    suite
+        .call_method(ism, "approve", manifest_args!(message))
+        .expect_commit_success();
+    let approved: bool = suite.call_method_success(ism, "approved", manifest_args!(message_id));
+    assert!(!approved);
+
+    // The approval of an earlier transaction can't be used to process the message
+    let receipt = process(&mut suite, mailbox, token);
+    assert!(format!("{:?}", receipt.expect_commit_failure())
+        .contains("Mailbox: ISM verification failed"));
+}
+
+#[test]
+fn test_verify_without_approval() {
+    let mut suite = common::setup();
+    let relayer = NonFungibleGlobalId::from_public_key(&suite.account.public_key);
+    let (_, ism, _) = setup_trusted_relayer_mailbox(&mut suite, rule!(require(relayer)));
+
+    // Any caller can call verify, e.g. an aggregation ISM
+    let verified: bool = suite.call_method_success(
+        ism,
+        "verify",
+        manifest_args!(Vec::<u8>::new(), hex::decode(PAYLOAD).unwrap()),
+    );
+    assert!(!verified);
+}